@@ -5,6 +5,7 @@
 //! - Setting up FFI bindings with bindgen
 //! - Managing static linking
 //! - Platform-specific configuration
+//! - Placing the data files next to the artifacts for the `bundled-data` feature
 
 use std::env;
 use std::fs;
@@ -12,12 +13,17 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use bindgen::RustEdition;
+use sha2::{Digest, Sha256};
 
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
     println!("cargo:rerun-if-changed=wrapper.h");
     println!("cargo:rerun-if-env-changed=LIBPOSTAL_SYSTEM");
     println!("cargo:rerun-if-env-changed=LIBPOSTAL_SKIP_BUILD");
+    println!("cargo:rerun-if-env-changed=LIBPOSTAL_BUNDLED_DATA_DIR");
+    println!("cargo:rerun-if-env-changed=LIBPOSTAL_BUNDLED_ARCHIVES");
+    println!("cargo:rerun-if-env-changed=LIBPOSTAL_BUNDLED_DATA_VERSION");
+    println!("cargo:rerun-if-changed=src/data/checksums.txt");
 
     // Check for skip build flag (useful for docs.rs)
     if env::var("LIBPOSTAL_SKIP_BUILD").is_ok() {
@@ -29,6 +35,10 @@ fn main() {
     let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
     let target = env::var("TARGET").unwrap();
 
+    if env::var("CARGO_FEATURE_BUNDLED_DATA").is_ok() {
+        bundle_data(&out_dir);
+    }
+
     // Check for required build tools (only if not skipping)
     check_build_dependencies();
 
@@ -451,3 +461,226 @@ fn copy_libpostal_data_executable(install_dir: &Path, out_dir: &Path) {
         );
     }
 }
+
+/// Data components, their archives and the version files recording them, as in `src/data.rs`
+const BUNDLED_COMPONENTS: &[(&str, &str, &str)] = &[
+    ("base", "libpostal_data.tar.gz", "base_data_file_version"),
+    ("parser", "parser.tar.gz", "parser_model_file_version"),
+    (
+        "language_classifier",
+        "language_classifier.tar.gz",
+        "language_classifier_model_file_version",
+    ),
+];
+const DEFAULT_BUNDLED_DATA_VERSION: &str = "v1.0.0";
+const BUNDLED_DATA_URL: &str = "https://github.com/openvenues/libpostal/releases/download";
+
+/// Where the Senzing-trained models are published, and the prefix of their versions
+const SENZING_DATA_URL: &str = "https://public-read-libpostal-data.s3.amazonaws.com";
+const SENZING_VERSION_PREFIX: &str = "senzing-";
+
+/// Expected SHA-256 checksums, the same manifest the crate compiles in
+const CHECKSUM_MANIFEST: &str = include_str!("src/data/checksums.txt");
+
+/// Name of the bundled data directory, next to the build's artifacts
+const BUNDLED_DATA_DIR_NAME: &str = "libpostal-data";
+
+/// The single generation a bundled data directory holds, see `src/data/generation.rs`
+const BUNDLED_GENERATION: &str = "generations/1";
+const BUNDLED_STAGING_DIR: &str = ".staging-build";
+
+/// Prepare the data files for the `bundled-data` feature
+///
+/// The data comes from the first of:
+/// - `LIBPOSTAL_BUNDLED_DATA_DIR`, a complete data directory, used where it is
+/// - `LIBPOSTAL_BUNDLED_ARCHIVES`, a directory holding the release archives
+/// - the libpostal release downloads
+///
+/// `LIBPOSTAL_BUNDLED_DATA_VERSION` selects the data version, `v1.0.0` by
+/// default. A `senzing-` version such as `senzing-v1.1.0` bundles the
+/// Senzing-trained models, so the runtime configuration must select
+/// `ParserModel::Senzing` to use them.
+///
+/// Archives and the files they extract to are checked against
+/// `src/data/checksums.txt`; a mismatch fails the build, and an archive
+/// without an entry is bundled with a warning. They are unpacked into a
+/// `libpostal-data` directory next to the build's binaries, e.g.
+/// `target/release/libpostal-data`, laid out as a single generation the
+/// `DataManager` reads like any other install. The data directory is passed
+/// to the crate as `LIBPOSTAL_BUNDLED_DATA_DIR`.
+fn bundle_data(out_dir: &Path) {
+    if let Ok(data_dir) = env::var("LIBPOSTAL_BUNDLED_DATA_DIR") {
+        let data_dir = fs::canonicalize(&data_dir)
+            .unwrap_or_else(|e| panic!("LIBPOSTAL_BUNDLED_DATA_DIR {data_dir}: {e}"));
+        println!(
+            "cargo:rustc-env=LIBPOSTAL_BUNDLED_DATA_DIR={}",
+            data_dir.display()
+        );
+        return;
+    }
+
+    let version = env::var("LIBPOSTAL_BUNDLED_DATA_VERSION")
+        .unwrap_or_else(|_| DEFAULT_BUNDLED_DATA_VERSION.to_string());
+
+    // OUT_DIR is target/<profile>/build/<package>-<hash>/out
+    let artifact_dir = out_dir
+        .ancestors()
+        .nth(3)
+        .expect("OUT_DIR is inside the target directory");
+    let data_dir = artifact_dir.join(BUNDLED_DATA_DIR_NAME);
+    let generation = data_dir.join(BUNDLED_GENERATION);
+    let is_bundled = |dir: &Path| {
+        BUNDLED_COMPONENTS.iter().all(|&(_, _, version_file)| {
+            fs::read_to_string(dir.join(version_file)).is_ok_and(|v| v.trim() == version)
+        })
+    };
+
+    if !is_bundled(&generation) {
+        let staging = data_dir.join(BUNDLED_STAGING_DIR);
+        // Components already extracted by an interrupted build are kept,
+        // unless they are of another version
+        let is_stale = BUNDLED_COMPONENTS.iter().any(|&(_, _, version_file)| {
+            fs::read_to_string(staging.join(version_file)).is_ok_and(|v| v.trim() != version)
+        });
+        if is_stale {
+            fs::remove_dir_all(&staging).expect("Failed to remove stale bundled data");
+        }
+        fs::create_dir_all(&staging).expect("Failed to create bundled data directory");
+
+        let archives = env::var("LIBPOSTAL_BUNDLED_ARCHIVES")
+            .ok()
+            .map(PathBuf::from);
+        for &(component, filename, version_file) in BUNDLED_COMPONENTS {
+            let version_path = staging.join(version_file);
+            if fs::read_to_string(&version_path).is_ok_and(|v| v.trim() == version) {
+                continue;
+            }
+
+            let archive = match &archives {
+                Some(dir) => dir.join(filename),
+                None => {
+                    let archive = out_dir.join(filename);
+                    download_data_archive(&version, filename, &archive);
+                    archive
+                }
+            };
+            println!("cargo:warning=Bundling {}", archive.display());
+            verify_bundled_archive(component, &version, filename, &archive);
+
+            let file = fs::File::open(&archive)
+                .unwrap_or_else(|e| panic!("Failed to open {}: {e}", archive.display()));
+            tar::Archive::new(flate2::read::GzDecoder::new(file))
+                .unpack(&staging)
+                .unwrap_or_else(|e| panic!("Failed to extract {}: {e}", archive.display()));
+            verify_bundled_files(component, &version, filename, &staging);
+            // Written last, so an interrupted build extracts the archive again
+            fs::write(&version_path, &version).expect("Failed to write version file");
+
+            if archives.is_none() {
+                fs::remove_file(&archive).ok();
+            }
+        }
+
+        // The build owns the directory, so replace whatever an earlier build
+        // left there, including data laid out before generations existed
+        for entry in fs::read_dir(&data_dir).expect("Failed to read bundled data directory") {
+            let path = entry.expect("Failed to read bundled data directory").path();
+            if path == staging {
+                continue;
+            }
+            if path.is_dir() {
+                fs::remove_dir_all(&path)
+            } else {
+                fs::remove_file(&path)
+            }
+            .unwrap_or_else(|e| panic!("Failed to remove {}: {e}", path.display()));
+        }
+        fs::create_dir_all(generation.parent().unwrap())
+            .expect("Failed to create bundled data directory");
+        fs::rename(&staging, &generation).expect("Failed to publish bundled data");
+    }
+    fs::write(data_dir.join("current"), format!("{BUNDLED_GENERATION}\n"))
+        .expect("Failed to write bundled data generation");
+
+    println!(
+        "cargo:rustc-env=LIBPOSTAL_BUNDLED_DATA_DIR={}",
+        data_dir.display()
+    );
+}
+
+/// Expected checksums of `component` at `version`, as `(path, sha256)`
+fn manifest_checksums<'a>(
+    component: &'a str,
+    version: &'a str,
+) -> impl Iterator<Item = (&'static str, &'static str)> + 'a {
+    CHECKSUM_MANIFEST
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(
+            move |line| match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [c, v, sha256, path] if c == component && v == version => Some((path, sha256)),
+                _ => None,
+            },
+        )
+}
+
+/// Check a data archive against the checksum manifest before it is unpacked
+fn verify_bundled_archive(component: &str, version: &str, filename: &str, archive: &Path) {
+    match manifest_checksums(component, version).find(|&(path, _)| path == filename) {
+        Some((_, expected)) => verify_bundled_file(archive, expected),
+        None => println!(
+            "cargo:warning=No checksum for {component} {version} in src/data/checksums.txt, bundling {filename} unverified"
+        ),
+    }
+}
+
+/// Check the files an archive extracted to against the checksum manifest
+fn verify_bundled_files(component: &str, version: &str, filename: &str, data_dir: &Path) {
+    for (path, expected) in manifest_checksums(component, version) {
+        if path != filename {
+            verify_bundled_file(&data_dir.join(path), expected);
+        }
+    }
+}
+
+fn verify_bundled_file(path: &Path, expected: &str) {
+    let mut file =
+        fs::File::open(path).unwrap_or_else(|e| panic!("Failed to open {}: {e}", path.display()));
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {e}", path.display()));
+    let actual = format!("{:x}", hasher.finalize());
+    if !actual.eq_ignore_ascii_case(expected) {
+        panic!(
+            "{} failed verification: expected SHA-256 {expected}, got {actual}",
+            path.display()
+        );
+    }
+}
+
+/// Download a data release archive to `path`
+fn download_data_archive(version: &str, filename: &str, path: &Path) {
+    let url = match version.strip_prefix(SENZING_VERSION_PREFIX) {
+        Some(release) => format!("{SENZING_DATA_URL}/{release}/{filename}"),
+        None => format!("{BUNDLED_DATA_URL}/{version}/{filename}"),
+    };
+    println!("cargo:warning=Downloading {url}");
+
+    // The parser model is about 2GB; don't time out mid-download
+    let client = reqwest::blocking::Client::builder()
+        .timeout(None)
+        .build()
+        .expect("Failed to create HTTP client");
+    let mut response = client
+        .get(&url)
+        .send()
+        .and_then(|response| response.error_for_status())
+        .unwrap_or_else(|e| panic!("Failed to download {url}: {e}"));
+
+    let mut file = fs::File::create(path)
+        .unwrap_or_else(|e| panic!("Failed to create {}: {e}", path.display()));
+    response
+        .copy_to(&mut file)
+        .unwrap_or_else(|e| panic!("Failed to download {url}: {e}"));
+}
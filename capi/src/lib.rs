@@ -0,0 +1,144 @@
+//! C ABI shared library for libpostal-rs.
+//!
+//! Builds `liblibpostal_rs.{so,dylib}` (or `libpostal_rs.dll`) exporting the
+//! functions declared in `include/libpostal_rs.h`:
+//!
+//! ```text
+//! cargo build --release -p libpostal-rs-capi
+//! ```
+//!
+//! The functions live in the `capi` module of libpostal-rs; this crate only
+//! links them into a shared library.
+
+pub use postal::capi::*;
+
+#[cfg(test)]
+mod tests {
+    use postal::capi::LprStatus;
+    use postal::{AddressNormalizer, AddressParser};
+    use std::ffi::{CStr, c_char, c_void};
+    use std::ptr;
+
+    // Declared the way a C caller sees them, so the calls go through the
+    // exported symbols rather than the Rust paths.
+    unsafe extern "C" {
+        fn lpr_last_error() -> *const c_char;
+        fn lpr_init(
+            data_dir: *const c_char,
+            auto_download: bool,
+            out: *mut *mut c_void,
+        ) -> LprStatus;
+        fn lpr_parser_new(
+            postal: *const c_void,
+            language: *const c_char,
+            country: *const c_char,
+            out: *mut *mut c_void,
+        ) -> LprStatus;
+        fn lpr_parse(
+            parser: *const c_void,
+            address: *const c_char,
+            out_json: *mut *mut c_char,
+        ) -> LprStatus;
+        fn lpr_parse_batch(
+            parser: *const c_void,
+            addresses: *const *const c_char,
+            count: usize,
+            out_json: *mut *mut c_char,
+        ) -> LprStatus;
+        fn lpr_normalizer_new(
+            postal: *const c_void,
+            languages: *const *const c_char,
+            language_count: usize,
+            out: *mut *mut c_void,
+        ) -> LprStatus;
+        fn lpr_expand(
+            normalizer: *const c_void,
+            address: *const c_char,
+            out_json: *mut *mut c_char,
+        ) -> LprStatus;
+    }
+
+    const INVALID_UTF8: &CStr = c"123 Main St \xff";
+
+    fn last_error() -> String {
+        let message = unsafe { lpr_last_error() };
+        assert!(!message.is_null());
+        unsafe { CStr::from_ptr(message) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn test_init_rejects_invalid_data_dir() {
+        let mut postal = ptr::null_mut();
+        let status = unsafe { lpr_init(INVALID_UTF8.as_ptr(), false, &mut postal) };
+
+        assert_eq!(status, LprStatus::InvalidArgument);
+        assert!(postal.is_null());
+        assert_eq!(last_error(), "data_dir is not valid UTF-8");
+    }
+
+    #[test]
+    fn test_null_handles() {
+        let mut handle = ptr::null_mut();
+        let status = unsafe { lpr_parser_new(ptr::null(), ptr::null(), ptr::null(), &mut handle) };
+        assert_eq!(status, LprStatus::InvalidArgument);
+        assert_eq!(last_error(), "postal must not be null");
+
+        let status = unsafe { lpr_normalizer_new(ptr::null(), ptr::null(), 0, &mut handle) };
+        assert_eq!(status, LprStatus::InvalidArgument);
+        assert_eq!(last_error(), "postal must not be null");
+        assert!(handle.is_null());
+
+        let mut json = ptr::null_mut();
+        let status = unsafe { lpr_parse(ptr::null(), c"123 Main St".as_ptr(), &mut json) };
+        assert_eq!(status, LprStatus::InvalidArgument);
+        assert_eq!(last_error(), "parser must not be null");
+
+        let status = unsafe { lpr_expand(ptr::null(), c"123 Main St".as_ptr(), &mut json) };
+        assert_eq!(status, LprStatus::InvalidArgument);
+        assert_eq!(last_error(), "normalizer must not be null");
+        assert!(json.is_null());
+    }
+
+    #[test]
+    fn test_parse_arguments() {
+        let parser = AddressParser::new();
+        let parser = &parser as *const AddressParser as *const c_void;
+        let mut json = ptr::null_mut();
+
+        let status = unsafe { lpr_parse(parser, ptr::null(), &mut json) };
+        assert_eq!(status, LprStatus::InvalidArgument);
+        assert_eq!(last_error(), "address must not be null");
+
+        let status = unsafe { lpr_parse(parser, INVALID_UTF8.as_ptr(), &mut json) };
+        assert_eq!(status, LprStatus::InvalidArgument);
+        assert_eq!(last_error(), "address is not valid UTF-8");
+
+        let status = unsafe { lpr_parse_batch(parser, ptr::null(), 2, &mut json) };
+        assert_eq!(status, LprStatus::InvalidArgument);
+        assert_eq!(last_error(), "addresses must not be null");
+
+        let addresses = [c"123 Main St".as_ptr(), INVALID_UTF8.as_ptr()];
+        let status = unsafe { lpr_parse_batch(parser, addresses.as_ptr(), 2, &mut json) };
+        assert_eq!(status, LprStatus::InvalidArgument);
+        assert_eq!(last_error(), "addresses[1] is not valid UTF-8");
+        assert!(json.is_null());
+    }
+
+    #[test]
+    fn test_expand_arguments() {
+        let normalizer = AddressNormalizer::new();
+        let normalizer = &normalizer as *const AddressNormalizer as *const c_void;
+        let mut json = ptr::null_mut();
+
+        let status = unsafe { lpr_expand(normalizer, ptr::null(), &mut json) };
+        assert_eq!(status, LprStatus::InvalidArgument);
+        assert_eq!(last_error(), "address must not be null");
+
+        let status = unsafe { lpr_expand(normalizer, INVALID_UTF8.as_ptr(), &mut json) };
+        assert_eq!(status, LprStatus::InvalidArgument);
+        assert_eq!(last_error(), "address is not valid UTF-8");
+        assert!(json.is_null());
+    }
+}
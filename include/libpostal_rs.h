@@ -0,0 +1,87 @@
+/*
+ * C interface to libpostal-rs.
+ *
+ * Build the shared library with
+ * `cargo build --release -p libpostal-rs-capi` and
+ * link against target/release/liblibpostal_rs.{so,dylib} (or the .dll).
+ *
+ * Every fallible function returns an lpr_status; on failure,
+ * lpr_last_error() describes what went wrong on the calling thread.
+ * Strings returned through `out_json` are owned by the caller and must be
+ * released with lpr_string_free(). Handles are released with their matching
+ * *_free function; passing NULL to any *_free function is a no-op.
+ *
+ * Parsers and normalizers are read-only and may be shared between threads.
+ */
+
+#ifndef LIBPOSTAL_RS_H
+#define LIBPOSTAL_RS_H
+
+#include <stdbool.h>
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum lpr_status {
+    LPR_OK = 0,
+    LPR_INVALID_ARGUMENT = 1,
+    LPR_INITIALIZATION = 2,
+    LPR_DATA = 3,
+    LPR_PARSE = 4,
+    LPR_NORMALIZATION = 5,
+    LPR_FFI = 6,
+    LPR_IO = 7,
+    LPR_NETWORK = 8,
+    LPR_PANIC = 9,
+    LPR_OTHER = 10,
+    LPR_SUBSYSTEM_DISABLED = 11
+} lpr_status;
+
+/* Opaque handles. */
+typedef struct lpr_postal lpr_postal;
+typedef struct lpr_parser lpr_parser;
+typedef struct lpr_normalizer lpr_normalizer;
+
+/* Message of the last failed call on this thread, or NULL after a success.
+ * Valid until the next call into this library on the same thread. */
+const char *lpr_last_error(void);
+
+/* Initialize libpostal from `data_dir` (NULL for the default location),
+ * downloading missing data files first when `auto_download` is true. */
+lpr_status lpr_init(const char *data_dir, bool auto_download, lpr_postal **out);
+void lpr_free(lpr_postal *postal);
+
+/* Create a parser; `language` and `country` are optional hints (may be NULL). */
+lpr_status lpr_parser_new(const lpr_postal *postal, const char *language,
+                          const char *country, lpr_parser **out);
+void lpr_parser_free(lpr_parser *parser);
+
+/* Parse one address into a JSON object of components. */
+lpr_status lpr_parse(const lpr_parser *parser, const char *address, char **out_json);
+
+/* Parse `count` addresses into {"results": [object|null, ...],
+ * "errors": [{"index": n, "error": "..."}, ...]}. Per-item failures are
+ * reported in "errors" and do not fail the call. */
+lpr_status lpr_parse_batch(const lpr_parser *parser, const char *const *addresses,
+                           size_t count, char **out_json);
+
+/* Create a normalizer for `language_count` language codes; pass 0 for
+ * automatic language detection. */
+lpr_status lpr_normalizer_new(const lpr_postal *postal, const char *const *languages,
+                              size_t language_count, lpr_normalizer **out);
+void lpr_normalizer_free(lpr_normalizer *normalizer);
+
+/* Expand one address into a JSON array of normalized strings. */
+lpr_status lpr_expand(const lpr_normalizer *normalizer, const char *address,
+                      char **out_json);
+
+/* Release a string returned by this library. */
+void lpr_string_free(char *value);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* LIBPOSTAL_RS_H */
@@ -0,0 +1,49 @@
+#!/bin/sh
+# Regenerate src/data/checksums.txt from the published data releases.
+#
+# Downloads every release archive the crate installs, and writes the SHA-256
+# of each archive and of each file it extracts to. Run it when a data
+# release is added to the crate, and commit the result.
+#
+#     scripts/update-checksums.sh [work-dir]
+set -eu
+
+root=$(cd "$(dirname "$0")/.." && pwd)
+manifest="$root/src/data/checksums.txt"
+work=${1:-$(mktemp -d)}
+
+github=https://github.com/openvenues/libpostal/releases/download
+senzing=https://public-read-libpostal-data.s3.amazonaws.com
+
+# <component> <installed version> <archive URL>
+releases="
+base v1.0.0 $github/v1.0.0/libpostal_data.tar.gz
+parser v1.0.0 $github/v1.0.0/parser.tar.gz
+language_classifier v1.0.0 $github/v1.0.0/language_classifier.tar.gz
+base senzing-v1.1.0 $senzing/v1.1.0/libpostal_data.tar.gz
+parser senzing-v1.1.0 $senzing/v1.1.0/parser.tar.gz
+language_classifier senzing-v1.1.0 $senzing/v1.1.0/language_classifier.tar.gz
+"
+
+entries="$work/entries"
+: > "$entries"
+
+echo "$releases" | while read -r component version url; do
+    [ -n "$component" ] || continue
+    archive="$work/$version/$(basename "$url")"
+    extracted="$work/$version/$component"
+    mkdir -p "$extracted"
+    [ -f "$archive" ] || curl -fL --retry 3 -o "$archive" "$url"
+
+    echo "$component $version $(sha256sum "$archive" | cut -d' ' -f1) $(basename "$archive")" >> "$entries"
+    tar -xzf "$archive" -C "$extracted"
+    (cd "$extracted" && find . -type f | sort | while read -r file; do
+        echo "$component $version $(sha256sum "$file" | cut -d' ' -f1) ${file#./}"
+    done) >> "$entries"
+done
+
+# Keep the header comment, replace the entries
+sed '/^[^#]/,$d' "$manifest" > "$work/checksums.txt"
+cat "$entries" >> "$work/checksums.txt"
+mv "$work/checksums.txt" "$manifest"
+echo "Wrote $(wc -l < "$entries") entries to $manifest"
@@ -0,0 +1,279 @@
+//! Apache Arrow and Parquet integration.
+//!
+//! Parses a Utf8 address column straight from an Arrow [`StringArray`] into a
+//! [`StructArray`] with one nullable Utf8 child per [`ParsedAddress::LABELS`]
+//! entry, and expands addresses into a `List<Utf8>` column. Input strings are
+//! borrowed from the array and handed to the parallel batch path, so no
+//! per-row `String` is built for the input side.
+//!
+//! Null inputs and rows that fail to parse produce null entries. With the
+//! `parquet` feature, [`read_parquet`] and [`write_parquet`] move record
+//! batches to and from Parquet files.
+//!
+//! ```rust,no_run
+//! use arrow::array::{Array, StringArray};
+//! use libpostal_rs::LibPostal;
+//!
+//! # async fn run() -> libpostal_rs::Result<()> {
+//! let postal = LibPostal::new().await?;
+//! let addresses = StringArray::from(vec![Some("123 Main St, New York, NY 10001"), None]);
+//!
+//! let parsed = libpostal_rs::arrow::parse_string_array(&postal.parser(), &addresses)?;
+//! assert_eq!(parsed.len(), 2);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::sync::Arc;
+
+use ::arrow::array::{
+    Array, ArrayRef, ListArray, ListBuilder, StringArray, StringBuilder, StructArray,
+};
+use ::arrow::buffer::NullBuffer;
+use ::arrow::datatypes::{DataType, Field, Fields, Schema};
+use ::arrow::error::ArrowError;
+use ::arrow::record_batch::RecordBatch;
+
+use crate::error::{Error, Result};
+use crate::normalizer::{AddressNormalizer, NormalizedAddress};
+use crate::parser::{AddressParser, ParsedAddress};
+
+/// Fields of the struct produced by [`parse_string_array`], one nullable Utf8 per label.
+pub fn parsed_address_fields() -> Fields {
+    ParsedAddress::LABELS
+        .iter()
+        .map(|label| Field::new(*label, DataType::Utf8, true))
+        .collect()
+}
+
+/// Data type of the list produced by [`normalize_string_array`].
+pub fn expansions_data_type() -> DataType {
+    DataType::List(Arc::new(Field::new_list_field(DataType::Utf8, true)))
+}
+
+/// Parse every address in `addresses` into a struct array of components.
+///
+/// The result has the same length as the input. Null inputs and addresses
+/// that fail to parse become null struct entries.
+pub fn parse_string_array(parser: &AddressParser, addresses: &StringArray) -> Result<StructArray> {
+    let (indices, values) = non_null_values(addresses);
+    let results = parser.parse_batch_parallel(&values)?;
+
+    let mut parsed: Vec<Option<ParsedAddress>> = (0..addresses.len()).map(|_| None).collect();
+    for (index, result) in indices.into_iter().zip(results) {
+        parsed[index] = result.ok();
+    }
+
+    let columns: Vec<ArrayRef> = ParsedAddress::LABELS
+        .iter()
+        .map(|label| {
+            let column: StringArray = parsed
+                .iter()
+                .map(|p| p.as_ref().and_then(|p| p.get(label)))
+                .collect();
+            Arc::new(column) as ArrayRef
+        })
+        .collect();
+    let nulls = NullBuffer::from_iter(parsed.iter().map(Option::is_some));
+
+    StructArray::try_new(parsed_address_fields(), columns, Some(nulls)).map_err(arrow_error)
+}
+
+/// Expand every address in `addresses` into a list of normalized strings.
+///
+/// The result has the same length as the input. Null inputs and addresses
+/// that fail to normalize become null lists.
+pub fn normalize_string_array(
+    normalizer: &AddressNormalizer,
+    addresses: &StringArray,
+) -> Result<ListArray> {
+    let (indices, values) = non_null_values(addresses);
+    let results = normalizer.normalize_batch_parallel(&values)?;
+
+    let mut normalized: Vec<Option<NormalizedAddress>> =
+        (0..addresses.len()).map(|_| None).collect();
+    for (index, result) in indices.into_iter().zip(results) {
+        normalized[index] = result.ok();
+    }
+
+    let mut builder = ListBuilder::new(StringBuilder::new());
+    for entry in &normalized {
+        match entry {
+            Some(normalized) => {
+                for expansion in &normalized.expansions {
+                    builder.values().append_value(expansion);
+                }
+                builder.append(true);
+            }
+            None => builder.append(false),
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// Parse the Utf8 column `column` of `batch` and append the result as `output_column`.
+pub fn parse_record_batch(
+    parser: &AddressParser,
+    batch: &RecordBatch,
+    column: &str,
+    output_column: &str,
+) -> Result<RecordBatch> {
+    let addresses = string_column(batch, column)?;
+    let parsed = parse_string_array(parser, addresses)?;
+    append_column(batch, output_column, Arc::new(parsed))
+}
+
+/// Expand the Utf8 column `column` of `batch` and append the result as `output_column`.
+pub fn normalize_record_batch(
+    normalizer: &AddressNormalizer,
+    batch: &RecordBatch,
+    column: &str,
+    output_column: &str,
+) -> Result<RecordBatch> {
+    let addresses = string_column(batch, column)?;
+    let expansions = normalize_string_array(normalizer, addresses)?;
+    append_column(batch, output_column, Arc::new(expansions))
+}
+
+/// Read every record batch from the Parquet file at `path`.
+#[cfg(feature = "parquet")]
+pub fn read_parquet<P: AsRef<std::path::Path>>(path: P) -> Result<Vec<RecordBatch>> {
+    use ::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    let file = std::fs::File::open(path.as_ref()).map_err(|e| {
+        Error::arrow_error(format!("Failed to open {}: {e}", path.as_ref().display()))
+    })?;
+    let reader = ParquetRecordBatchReaderBuilder::try_new(file)
+        .and_then(|builder| builder.build())
+        .map_err(parquet_error)?;
+    reader
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(arrow_error)
+}
+
+/// Write `batches` to a Parquet file at `path`.
+///
+/// All batches must share the schema of the first one.
+#[cfg(feature = "parquet")]
+pub fn write_parquet<P: AsRef<std::path::Path>>(path: P, batches: &[RecordBatch]) -> Result<()> {
+    use ::parquet::arrow::ArrowWriter;
+
+    let schema = batches
+        .first()
+        .ok_or_else(|| Error::arrow_error("No record batches to write"))?
+        .schema();
+    let file = std::fs::File::create(path.as_ref()).map_err(|e| {
+        Error::arrow_error(format!("Failed to create {}: {e}", path.as_ref().display()))
+    })?;
+
+    let mut writer = ArrowWriter::try_new(file, schema, None).map_err(parquet_error)?;
+    for batch in batches {
+        writer.write(batch).map_err(parquet_error)?;
+    }
+    writer.close().map_err(parquet_error)?;
+    Ok(())
+}
+
+/// Row indices and borrowed values of the non-null entries in `array`.
+fn non_null_values(array: &StringArray) -> (Vec<usize>, Vec<&str>) {
+    array
+        .iter()
+        .enumerate()
+        .filter_map(|(index, value)| value.map(|value| (index, value)))
+        .unzip()
+}
+
+fn string_column<'a>(batch: &'a RecordBatch, column: &str) -> Result<&'a StringArray> {
+    batch
+        .column_by_name(column)
+        .ok_or_else(|| Error::arrow_error(format!("Record batch has no column named {column:?}")))?
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .ok_or_else(|| Error::arrow_error(format!("Column {column:?} is not a Utf8 column")))
+}
+
+fn append_column(batch: &RecordBatch, name: &str, array: ArrayRef) -> Result<RecordBatch> {
+    let schema = batch.schema();
+    let mut fields: Vec<_> = schema.fields().iter().cloned().collect();
+    fields.push(Arc::new(Field::new(name, array.data_type().clone(), true)));
+
+    let mut columns = batch.columns().to_vec();
+    columns.push(array);
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).map_err(arrow_error)
+}
+
+fn arrow_error(error: ArrowError) -> Error {
+    Error::arrow_error(error.to_string())
+}
+
+#[cfg(feature = "parquet")]
+fn parquet_error(error: ::parquet::errors::ParquetError) -> Error {
+    Error::arrow_error(format!("Parquet: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch() -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("address", DataType::Utf8, true),
+        ]);
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(::arrow::array::Int64Array::from(vec![1, 2])),
+                Arc::new(StringArray::from(vec![Some("123 Main St"), None])),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_non_null_values() {
+        let array = StringArray::from(vec![Some("a"), None, Some("c")]);
+        let (indices, values) = non_null_values(&array);
+
+        assert_eq!(indices, vec![0, 2]);
+        assert_eq!(values, vec!["a", "c"]);
+    }
+
+    #[test]
+    fn test_parsed_address_fields() {
+        let fields = parsed_address_fields();
+
+        assert_eq!(fields.len(), ParsedAddress::LABELS.len());
+        assert_eq!(fields[0].name(), "house_number");
+        assert!(fields.iter().all(|f| f.is_nullable()));
+    }
+
+    #[test]
+    fn test_column_errors() {
+        let batch = batch();
+
+        assert!(matches!(
+            string_column(&batch, "missing"),
+            Err(Error::ArrowError { .. })
+        ));
+        assert!(matches!(
+            string_column(&batch, "id"),
+            Err(Error::ArrowError { .. })
+        ));
+        assert_eq!(string_column(&batch, "address").unwrap().len(), 2);
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("addresses.parquet");
+
+        write_parquet(&path, &[batch()]).unwrap();
+        let batches = read_parquet(&path).unwrap();
+
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0], batch());
+    }
+}
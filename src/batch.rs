@@ -0,0 +1,182 @@
+//! Per-item result reporting for batch operations.
+
+use crate::error::{Error, Result};
+
+/// How a batch operation reacts to a failing item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BatchMode {
+    /// Stop at the first failure; the report holds everything up to and including it
+    FailFast,
+    /// Keep going and only count failures
+    Skip,
+    /// Keep going and record every failure with its index and error
+    #[default]
+    Collect,
+}
+
+/// A single failed item in a batch.
+#[derive(Debug)]
+pub struct BatchFailure {
+    /// Position of the item in the input slice
+    pub index: usize,
+    /// The input that failed
+    pub input: String,
+    /// The error returned for this item
+    pub error: Error,
+}
+
+/// Outcome of a batch operation with per-item successes and failures.
+///
+/// Successes keep their input index so results can be lined up with the
+/// original rows even when some items failed.
+#[derive(Debug)]
+pub struct BatchReport<T> {
+    /// Successful results as `(input index, value)`, in input order
+    pub successes: Vec<(usize, T)>,
+    /// Recorded failures, in input order (empty in [`BatchMode::Skip`])
+    pub failures: Vec<BatchFailure>,
+    /// Number of items in the input
+    pub total: usize,
+    /// Number of failures that were counted but not recorded
+    pub skipped: usize,
+}
+
+impl<T> BatchReport<T> {
+    /// Build a report from per-item results, applying the given mode.
+    ///
+    /// `results` is consumed lazily, so in [`BatchMode::FailFast`] no item
+    /// after the first failure is evaluated. This makes it easy to report on
+    /// any per-item operation:
+    ///
+    /// ```rust,no_run
+    /// use libpostal_rs::{BatchMode, BatchReport, LibPostal};
+    ///
+    /// # async fn run() -> libpostal_rs::Result<()> {
+    /// let postal = LibPostal::new().await?;
+    /// let inputs = ["Rue de la Paix, Paris", "Calle Mayor, Madrid"];
+    /// let report = BatchReport::from_results(
+    ///     &inputs,
+    ///     inputs.iter().map(|input| postal.classify_language(input)),
+    ///     BatchMode::Collect,
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_results<I>(inputs: &[&str], results: I, mode: BatchMode) -> Self
+    where
+        I: IntoIterator<Item = Result<T>>,
+    {
+        let mut report = BatchReport {
+            successes: Vec::with_capacity(inputs.len()),
+            failures: Vec::new(),
+            total: inputs.len(),
+            skipped: 0,
+        };
+
+        for (index, (input, result)) in inputs.iter().zip(results).enumerate() {
+            match result {
+                Ok(value) => report.successes.push((index, value)),
+                Err(_) if mode == BatchMode::Skip => report.skipped += 1,
+                Err(error) => {
+                    report.failures.push(BatchFailure {
+                        index,
+                        input: input.to_string(),
+                        error,
+                    });
+                    if mode == BatchMode::FailFast {
+                        break;
+                    }
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Number of items that succeeded.
+    pub fn succeeded(&self) -> usize {
+        self.successes.len()
+    }
+
+    /// Number of items that failed, whether recorded or skipped.
+    pub fn failed(&self) -> usize {
+        self.failures.len() + self.skipped
+    }
+
+    /// Number of items that were attempted.
+    ///
+    /// This is less than `total` when a fail-fast batch stopped early.
+    pub fn processed(&self) -> usize {
+        self.succeeded() + self.failed()
+    }
+
+    /// Check if every input item succeeded.
+    pub fn is_complete(&self) -> bool {
+        self.succeeded() == self.total
+    }
+
+    /// Iterate over the successful values in input order.
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.successes.iter().map(|(_, value)| value)
+    }
+
+    /// Convert into the successful values, dropping indices and failures.
+    pub fn into_values(self) -> Vec<T> {
+        self.successes.into_iter().map(|(_, value)| value).collect()
+    }
+
+    /// Convert into a plain result, returning the first recorded failure as an error.
+    pub fn into_result(self) -> Result<Vec<T>> {
+        match self.failures.into_iter().next() {
+            Some(failure) => Err(failure.error),
+            None => Ok(self.successes.into_iter().map(|(_, value)| value).collect()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results() -> Vec<Result<usize>> {
+        vec![
+            Ok(1),
+            Err(Error::parse_error("bad")),
+            Ok(3),
+            Err(Error::parse_error("worse")),
+        ]
+    }
+
+    const INPUTS: &[&str] = &["a", "b", "c", "d"];
+
+    #[test]
+    fn test_collect_mode() {
+        let report = BatchReport::from_results(INPUTS, results(), BatchMode::Collect);
+        assert_eq!(report.total, 4);
+        assert_eq!(report.succeeded(), 2);
+        assert_eq!(report.failed(), 2);
+        assert_eq!(report.failures[0].index, 1);
+        assert_eq!(report.failures[1].input, "d");
+        assert_eq!(report.successes[1], (2, 3));
+        assert!(!report.is_complete());
+    }
+
+    #[test]
+    fn test_skip_mode() {
+        let report = BatchReport::from_results(INPUTS, results(), BatchMode::Skip);
+        assert!(report.failures.is_empty());
+        assert_eq!(report.skipped, 2);
+        assert_eq!(report.failed(), 2);
+        assert_eq!(report.processed(), 4);
+        assert_eq!(report.into_values(), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_fail_fast_mode() {
+        let report = BatchReport::from_results(INPUTS, results(), BatchMode::FailFast);
+        assert_eq!(report.processed(), 2);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].index, 1);
+        assert!(report.into_result().is_err());
+    }
+}
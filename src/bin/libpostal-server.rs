@@ -0,0 +1,35 @@
+//! HTTP server for libpostal-rs, request-compatible with libpostal-rest.
+//!
+//! Listens on `LISTEN_HOST:LISTEN_PORT` (default `0.0.0.0:8080`) like
+//! libpostal-rest, and reads data from `LIBPOSTAL_DATA_DIR` when set.
+//!
+//! Run with: cargo run --features server --bin libpostal-server
+
+use std::process::ExitCode;
+
+use libpostal_rs::LibPostalConfig;
+use libpostal_rs::server::{serve, shutdown_signal};
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let host = std::env::var("LISTEN_HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
+    let port = std::env::var("LISTEN_PORT").unwrap_or_else(|_| "8080".to_string());
+    let addr = format!("{host}:{port}");
+
+    let listener = match tokio::net::TcpListener::bind(&addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("failed to listen on {addr}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    eprintln!("listening on {addr}");
+
+    match serve(listener, LibPostalConfig::default(), shutdown_signal()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("server error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
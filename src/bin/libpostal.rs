@@ -0,0 +1,845 @@
+//! Command-line tool for libpostal-rs.
+//!
+//! Reads one address per line (or one column of a CSV file) from stdin or
+//! files and writes JSON lines, CSV or a table to stdout.
+//!
+//! Run with: cargo run --features cli --bin libpostal -- parse addresses.txt
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde_json::{Map, Value, json};
+
+use libpostal_rs::data::{
+    DataComponent, DataManager, DownloadStage, ParserModel, ProgressCallback, get_component_info,
+};
+use libpostal_rs::profiling::format_bytes;
+use libpostal_rs::{
+    AddressNormalizer, BatchMode, BatchReport, Country, Language, LibPostal, LibPostalConfig,
+    NormalizationLevel,
+};
+
+type CliResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// Parse, normalize and classify addresses with libpostal.
+#[derive(Debug, Parser)]
+#[command(name = "libpostal", version)]
+struct Cli {
+    /// Data directory (defaults to LIBPOSTAL_DATA_DIR or the user cache directory)
+    #[arg(long, global = true)]
+    data_dir: Option<PathBuf>,
+
+    /// Fail instead of downloading missing data files
+    #[arg(long, global = true)]
+    no_download: bool,
+
+    /// Model set to install and use
+    #[arg(long, global = true, value_enum, default_value_t = Model::Default)]
+    parser_model: Model,
+
+    /// Path or URL of a release manifest naming the data versions to upgrade to
+    #[arg(long, global = true)]
+    release_manifest: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Parse addresses into labeled components
+    Parse {
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+        /// Language hint (e.g. "en", "fr")
+        #[arg(long)]
+        language: Option<String>,
+        /// Country hint (e.g. "US", "FR")
+        #[arg(long)]
+        country: Option<String>,
+    },
+    /// Expand addresses into normalized variants
+    Expand {
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+        #[command(flatten)]
+        normalize: NormalizeArgs,
+    },
+    /// Detect the language of each address
+    ClassifyLanguage {
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Group addresses that share a normalized expansion
+    Dedupe {
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+        #[command(flatten)]
+        normalize: NormalizeArgs,
+    },
+    /// Inspect and manage the libpostal data directory
+    Data {
+        #[command(subcommand)]
+        action: DataAction,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum DataAction {
+    /// Show where data lives and whether it is complete
+    Status {
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Download any missing data files
+    Download,
+    /// Check data file checksums
+    Verify,
+    /// Install data from a release archive, or a directory of them, without downloading
+    Import {
+        /// `libpostal_data.tar.gz`, `parser.tar.gz`, `language_classifier.tar.gz` or a directory
+        path: PathBuf,
+    },
+    /// Compare installed data versions with the ones to roll out
+    Updates {
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Install the versions to roll out where they differ from the installed ones
+    Upgrade {
+        /// Component to upgrade; all of them when omitted
+        #[arg(value_enum)]
+        component: Option<Component>,
+    },
+    /// Remove the data directory
+    Clean,
+}
+
+#[derive(Debug, Args)]
+struct InputArgs {
+    /// Input files, one address per line; reads stdin when empty or "-"
+    files: Vec<PathBuf>,
+
+    /// Read addresses from this column of CSV input instead of whole lines
+    #[arg(long)]
+    column: Option<String>,
+
+    /// Field delimiter for CSV input
+    #[arg(long, default_value_t = ',')]
+    delimiter: char,
+}
+
+#[derive(Debug, Args)]
+struct OutputArgs {
+    /// Output format
+    #[arg(long, short, value_enum, default_value_t = OutputFormat::Jsonl)]
+    format: OutputFormat,
+}
+
+#[derive(Debug, Args)]
+struct NormalizeArgs {
+    /// Language to expand for (e.g. "en"); may be repeated
+    #[arg(long)]
+    language: Vec<String>,
+
+    /// Normalization preset
+    #[arg(long, value_enum, default_value_t = Level::Medium)]
+    level: Level,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    /// One JSON object per line
+    Jsonl,
+    /// CSV with a header row
+    Csv,
+    /// Aligned columns for reading in a terminal
+    Table,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Component {
+    Base,
+    Parser,
+    LanguageClassifier,
+}
+
+impl From<Component> for DataComponent {
+    fn from(component: Component) -> Self {
+        match component {
+            Component::Base => DataComponent::Base,
+            Component::Parser => DataComponent::Parser,
+            Component::LanguageClassifier => DataComponent::LanguageClassifier,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Model {
+    /// The libpostal project's models
+    Default,
+    /// The Senzing-trained models from libpostal v1.1
+    Senzing,
+}
+
+impl From<Model> for ParserModel {
+    fn from(model: Model) -> Self {
+        match model {
+            Model::Default => ParserModel::Default,
+            Model::Senzing => ParserModel::Senzing,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Level {
+    Light,
+    Medium,
+    Aggressive,
+}
+
+impl From<Level> for NormalizationLevel {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::Light => NormalizationLevel::Light,
+            Level::Medium => NormalizationLevel::Medium,
+            Level::Aggressive => NormalizationLevel::Aggressive,
+        }
+    }
+}
+
+type Record = Map<String, Value>;
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {e}");
+            let mut source = e.source();
+            while let Some(cause) = source {
+                eprintln!("  caused by: {cause}");
+                source = cause.source();
+            }
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(cli: Cli) -> CliResult<ExitCode> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    let mut builder = LibPostalConfig::builder()
+        .auto_download_data(!cli.no_download)
+        .parser_model(cli.parser_model.into())
+        .download_progress(progress_printer());
+    if let Some(dir) = &cli.data_dir {
+        builder = builder.data_dir(dir);
+    }
+    let mut config = builder.build();
+    config.data_config.release_manifest = cli.release_manifest;
+
+    if let Command::Data { action } = cli.command {
+        let manager = DataManager::with_config(config.data_config);
+        return run_data(&runtime, &manager, action);
+    }
+
+    let postal = runtime.block_on(LibPostal::with_config(config))?;
+
+    let (records, output) = match cli.command {
+        Command::Parse {
+            input,
+            output,
+            language,
+            country,
+        } => {
+            let inputs = read_inputs(&input)?;
+            let inputs: Vec<&str> = inputs.iter().map(String::as_str).collect();
+
+            let mut parser = postal.parser();
+            if let Some(language) = language {
+                parser = parser.with_language(Language::from_str(&language));
+            }
+            if let Some(country) = country {
+                parser = parser.with_country(Country::from_str(&country));
+            }
+
+            let report = parser.parse_batch_report(&inputs, BatchMode::Collect);
+            let records = report_records(&inputs, report, |parsed, record| {
+                for label in libpostal_rs::ParsedAddress::LABELS {
+                    if let Some(value) = parsed.get(label) {
+                        record.insert(label.to_string(), json!(value));
+                    }
+                }
+                if !parsed.other.is_empty() {
+                    record.insert("other".to_string(), json!(parsed.other));
+                }
+            });
+            (records, output)
+        }
+        Command::Expand {
+            input,
+            output,
+            normalize,
+        } => {
+            let inputs = read_inputs(&input)?;
+            let inputs: Vec<&str> = inputs.iter().map(String::as_str).collect();
+
+            let report =
+                normalizer(&postal, &normalize).normalize_batch_report(&inputs, BatchMode::Collect);
+            let records = report_records(&inputs, report, |normalized, record| {
+                record.insert("expansions".to_string(), json!(normalized.expansions));
+            });
+            (records, output)
+        }
+        Command::ClassifyLanguage { input, output } => {
+            let inputs = read_inputs(&input)?;
+            let inputs: Vec<&str> = inputs.iter().map(String::as_str).collect();
+
+            let report = BatchReport::from_results(
+                &inputs,
+                inputs
+                    .iter()
+                    .map(|address| postal.classify_language(address)),
+                BatchMode::Collect,
+            );
+            let records = report_records(&inputs, report, |candidates, record| {
+                let top = candidates.first();
+                record.insert("language".to_string(), json!(top.map(|c| &c.language)));
+                record.insert("probability".to_string(), json!(top.map(|c| c.probability)));
+                record.insert("candidates".to_string(), json!(candidates));
+            });
+            (records, output)
+        }
+        Command::Dedupe {
+            input,
+            output,
+            normalize,
+        } => {
+            let inputs = read_inputs(&input)?;
+            let inputs: Vec<&str> = inputs.iter().map(String::as_str).collect();
+
+            let report =
+                normalizer(&postal, &normalize).normalize_batch_report(&inputs, BatchMode::Collect);
+            (dedupe_records(&inputs, report), output)
+        }
+        Command::Data { .. } => unreachable!("handled above"),
+    };
+
+    let failed = records.iter().any(|record| record.contains_key("error"));
+    write_records(&records, output.format, &mut io::stdout().lock())?;
+
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+fn run_data(
+    runtime: &tokio::runtime::Runtime,
+    manager: &DataManager,
+    action: DataAction,
+) -> CliResult<ExitCode> {
+    match action {
+        DataAction::Status { output } => {
+            let size = manager.data_size()?;
+            let mut record = Record::new();
+            record.insert(
+                "data_dir".to_string(),
+                json!(manager.data_dir().display().to_string()),
+            );
+            record.insert(
+                "active_dir".to_string(),
+                json!(manager.active_data_dir().display().to_string()),
+            );
+            record.insert("available".to_string(), json!(manager.is_data_available()));
+            record.insert(
+                "parser_model".to_string(),
+                json!(manager.installed_parser_model().to_string()),
+            );
+            let versions: Map<String, Value> = manager
+                .installed_versions()?
+                .into_iter()
+                .map(|(component, version)| (component.to_string(), json!(version)))
+                .collect();
+            record.insert("versions".to_string(), Value::Object(versions));
+            record.insert("size_bytes".to_string(), json!(size));
+            record.insert("size".to_string(), json!(format_bytes(size as usize)));
+            write_records(&[record], output.format, &mut io::stdout().lock())?;
+        }
+        DataAction::Download => {
+            runtime.block_on(manager.download_data())?;
+            eprintln!("data ready in {}", manager.data_dir().display());
+        }
+        DataAction::Verify => {
+            let report = manager.verify_integrity()?;
+            for file in report.corrupted() {
+                eprintln!("{}: {}", file.path.display(), file.status);
+            }
+            eprintln!(
+                "{} verified, {} without checksum, {} corrupted in {}",
+                report.verified(),
+                report.unverified(),
+                report.corrupted().count(),
+                manager.data_dir().display()
+            );
+            if !report.is_ok() {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        DataAction::Import { path } => {
+            let imported = if path.is_dir() {
+                manager.import_directory(&path)?
+            } else {
+                let component = archive_component(&path)?;
+                manager.import_archive(&path, component)?;
+                vec![component]
+            };
+            for component in imported {
+                eprintln!(
+                    "imported {} into {}",
+                    get_component_info(component).name,
+                    manager.data_dir().display()
+                );
+            }
+        }
+        DataAction::Updates { output } => {
+            let records: Vec<Record> = runtime
+                .block_on(manager.check_for_updates())?
+                .into_iter()
+                .map(|update| {
+                    let mut record = Record::new();
+                    record.insert("component".to_string(), json!(update.component.to_string()));
+                    record.insert("installed".to_string(), json!(update.installed));
+                    record.insert("target".to_string(), json!(update.target));
+                    record.insert("pinned".to_string(), json!(update.pinned));
+                    record.insert("update_available".to_string(), json!(update.is_available()));
+                    record
+                })
+                .collect();
+            write_records(&records, output.format, &mut io::stdout().lock())?;
+        }
+        DataAction::Upgrade { component } => {
+            let component = component.map_or(DataComponent::All, DataComponent::from);
+            let installed = runtime.block_on(manager.upgrade(component))?;
+            if installed.is_empty() {
+                eprintln!("data in {} is up to date", manager.data_dir().display());
+            }
+            for update in installed {
+                eprintln!(
+                    "upgraded {} from {} to {}",
+                    get_component_info(update.component).name,
+                    update.installed.as_deref().unwrap_or("unversioned"),
+                    update.target
+                );
+            }
+        }
+        DataAction::Clean => {
+            manager.cleanup()?;
+            eprintln!("removed {}", manager.data_dir().display());
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Find the component a release archive belongs to from its file name.
+fn archive_component(path: &Path) -> CliResult<DataComponent> {
+    let components = [
+        DataComponent::Base,
+        DataComponent::Parser,
+        DataComponent::LanguageClassifier,
+    ];
+    let name = path.file_name().and_then(|name| name.to_str());
+    components
+        .into_iter()
+        .find(|&component| name == Some(get_component_info(component).filename.as_str()))
+        .ok_or_else(|| {
+            format!(
+                "{} is not a libpostal data archive; expected libpostal_data.tar.gz, parser.tar.gz or language_classifier.tar.gz",
+                path.display()
+            )
+            .into()
+        })
+}
+
+/// Print data download progress to stderr, redrawing one line on a terminal.
+fn progress_printer() -> ProgressCallback {
+    let terminal = io::stderr().is_terminal();
+    ProgressCallback::new(move |progress| {
+        let name = get_component_info(progress.component).name;
+        match progress.stage {
+            DownloadStage::Downloading if terminal => {
+                let total = if progress.total_bytes > 0 {
+                    format!(" of {}", format_bytes(progress.total_bytes as usize))
+                } else {
+                    String::new()
+                };
+                eprint!(
+                    "\r\x1b[Kdownloading {name}: {}{total} ({}/s)",
+                    format_bytes(progress.downloaded_bytes as usize),
+                    format_bytes(progress.speed_bps as usize)
+                );
+            }
+            DownloadStage::Downloading => {}
+            DownloadStage::Verifying if terminal => eprint!("\r\x1b[Kverifying {name}"),
+            DownloadStage::Verifying => eprintln!("verifying {name}"),
+            DownloadStage::Extracting if terminal => eprint!("\r\x1b[Kextracting {name}"),
+            DownloadStage::Extracting => eprintln!("extracting {name}"),
+            DownloadStage::Complete if terminal => eprintln!("\r\x1b[K{name} ready"),
+            DownloadStage::Complete => eprintln!("{name} ready"),
+        }
+    })
+}
+
+fn normalizer(postal: &LibPostal, args: &NormalizeArgs) -> AddressNormalizer {
+    let mut normalizer = postal.normalizer().with_level(args.level.into());
+    if !args.language.is_empty() {
+        let languages: Vec<Language> = args
+            .language
+            .iter()
+            .map(|code| Language::from_str(code))
+            .collect();
+        normalizer = normalizer.with_languages(&languages);
+    }
+    normalizer
+}
+
+/// Read addresses from every input source, in order.
+fn read_inputs(args: &InputArgs) -> CliResult<Vec<String>> {
+    let stdin = [PathBuf::from("-")];
+    let files = if args.files.is_empty() {
+        &stdin[..]
+    } else {
+        &args.files[..]
+    };
+
+    let mut addresses = Vec::new();
+    for path in files {
+        let reader: Box<dyn Read> = if path.as_os_str() == "-" {
+            Box::new(io::stdin().lock())
+        } else {
+            Box::new(
+                File::open(path).map_err(|e| format!("failed to open {}: {e}", path.display()))?,
+            )
+        };
+
+        match &args.column {
+            Some(column) => read_csv_column(reader, column, args.delimiter, &mut addresses)?,
+            None => {
+                for line in BufReader::new(reader).lines() {
+                    let line = line?;
+                    let line = line.trim_end_matches('\r');
+                    if !line.trim().is_empty() {
+                        addresses.push(line.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(addresses)
+}
+
+fn read_csv_column(
+    reader: impl Read,
+    column: &str,
+    delimiter: char,
+    addresses: &mut Vec<String>,
+) -> CliResult<()> {
+    let delimiter = u8::try_from(delimiter).map_err(|_| "CSV delimiter must be a single byte")?;
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .from_reader(reader);
+
+    let index = reader
+        .headers()?
+        .iter()
+        .position(|header| header == column)
+        .ok_or_else(|| format!("CSV input has no column named {column:?}"))?;
+
+    for row in reader.records() {
+        addresses.push(row?.get(index).unwrap_or_default().to_string());
+    }
+    Ok(())
+}
+
+/// One record per input holding just the `input` field.
+fn input_records(inputs: &[&str]) -> Vec<Record> {
+    inputs
+        .iter()
+        .map(|input| {
+            let mut record = Record::new();
+            record.insert("input".to_string(), json!(input));
+            record
+        })
+        .collect()
+}
+
+/// Turn a batch report into one record per input, with an `error` field for failures.
+fn report_records<T>(
+    inputs: &[&str],
+    report: BatchReport<T>,
+    mut fill: impl FnMut(&T, &mut Record),
+) -> Vec<Record> {
+    let mut records = input_records(inputs);
+    for (index, value) in &report.successes {
+        fill(value, &mut records[*index]);
+    }
+    for failure in &report.failures {
+        records[failure.index].insert("error".to_string(), json!(failure.error.to_string()));
+    }
+
+    records
+}
+
+/// Group inputs whose expansions overlap, pointing each duplicate at the first row of its group.
+fn dedupe_records(
+    inputs: &[&str],
+    report: BatchReport<libpostal_rs::NormalizedAddress>,
+) -> Vec<Record> {
+    // Union-find over input indices, always keeping the smallest index as the root.
+    let mut parent: Vec<usize> = (0..inputs.len()).collect();
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    let mut first_seen: HashMap<&str, usize> = HashMap::new();
+    for (index, normalized) in &report.successes {
+        for expansion in &normalized.expansions {
+            let other = *first_seen.entry(expansion.as_str()).or_insert(*index);
+            let (a, b) = (find(&mut parent, *index), find(&mut parent, other));
+            parent[a.max(b)] = a.min(b);
+        }
+    }
+
+    let mut records = input_records(inputs);
+    for (index, _) in &report.successes {
+        let group = find(&mut parent, *index);
+        let record = &mut records[*index];
+        record.insert("group".to_string(), json!(group));
+        record.insert(
+            "duplicate_of".to_string(),
+            json!((group != *index).then_some(group)),
+        );
+    }
+    for failure in &report.failures {
+        records[failure.index].insert("error".to_string(), json!(failure.error.to_string()));
+    }
+
+    records
+}
+
+fn write_records(records: &[Record], format: OutputFormat, out: &mut impl Write) -> CliResult<()> {
+    match format {
+        OutputFormat::Jsonl => {
+            for record in records {
+                serde_json::to_writer(&mut *out, record)?;
+                writeln!(out)?;
+            }
+        }
+        OutputFormat::Csv => {
+            let columns = columns(records);
+            let mut writer = csv::Writer::from_writer(out);
+            writer.write_record(&columns)?;
+            for record in records {
+                writer.write_record(columns.iter().map(|column| cell(record.get(column))))?;
+            }
+            writer.flush()?;
+        }
+        OutputFormat::Table => {
+            let columns = columns(records);
+            let rows: Vec<Vec<String>> = records
+                .iter()
+                .map(|record| {
+                    columns
+                        .iter()
+                        .map(|column| cell(record.get(column)))
+                        .collect()
+                })
+                .collect();
+
+            let widths: Vec<usize> = columns
+                .iter()
+                .enumerate()
+                .map(|(i, column)| {
+                    rows.iter()
+                        .map(|row| row[i].chars().count())
+                        .chain([column.chars().count()])
+                        .max()
+                        .unwrap_or_default()
+                })
+                .collect();
+
+            let print_row = |out: &mut dyn Write, cells: &[String]| -> io::Result<()> {
+                let line: Vec<String> = cells
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{cell:<width$}"))
+                    .collect();
+                writeln!(out, "{}", line.join("  ").trim_end())
+            };
+
+            print_row(out, &columns)?;
+            let rule: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+            print_row(out, &rule)?;
+            for row in &rows {
+                print_row(out, row)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Union of record keys in first-seen order.
+fn columns(records: &[Record]) -> Vec<String> {
+    let mut columns: Vec<String> = Vec::new();
+    for record in records {
+        for key in record.keys() {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+    }
+    columns
+}
+
+/// Render a JSON value as a flat CSV/table cell.
+fn cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Array(items)) if items.iter().all(Value::is_string) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join("; "),
+        Some(other) => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libpostal_rs::{Error, NormalizedAddress};
+
+    fn normalized(input: &str, expansions: &[&str]) -> libpostal_rs::Result<NormalizedAddress> {
+        Ok(NormalizedAddress {
+            original: input.to_string(),
+            expansions: expansions.iter().map(|e| e.to_string()).collect(),
+        })
+    }
+
+    fn rendered(records: &[Record], format: OutputFormat) -> String {
+        let mut out = Vec::new();
+        write_records(records, format, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_dedupe_records() {
+        let inputs = [
+            "1 Main St",
+            "1 Main Street",
+            "bad",
+            "9 Oak Ave",
+            "1 main st",
+        ];
+        let results = vec![
+            normalized(inputs[0], &["1 main street"]),
+            normalized(inputs[1], &["1 main street", "1 main saint"]),
+            Err(Error::normalization_error("failed")),
+            normalized(inputs[3], &["9 oak avenue"]),
+            normalized(inputs[4], &["1 main saint"]),
+        ];
+        let report = BatchReport::from_results(&inputs, results, BatchMode::Collect);
+
+        let records = dedupe_records(&inputs, report);
+        let groups: Vec<_> = records.iter().map(|r| r.get("group").cloned()).collect();
+        assert_eq!(
+            groups,
+            [
+                Some(json!(0)),
+                Some(json!(0)),
+                None,
+                Some(json!(3)),
+                Some(json!(0))
+            ]
+        );
+        assert_eq!(records[0]["duplicate_of"], Value::Null);
+        assert_eq!(records[4]["duplicate_of"], json!(0));
+        assert!(records[2]["error"].as_str().unwrap().contains("failed"));
+    }
+
+    #[test]
+    fn test_read_csv_column() {
+        let csv = "id;address\n1;\"1 Main St; Apt 2\"\n2;9 Oak Ave\n";
+        let mut addresses = Vec::new();
+        read_csv_column(csv.as_bytes(), "address", ';', &mut addresses).unwrap();
+        assert_eq!(addresses, ["1 Main St; Apt 2", "9 Oak Ave"]);
+
+        let error = read_csv_column(csv.as_bytes(), "street", ';', &mut addresses).unwrap_err();
+        assert!(error.to_string().contains("\"street\""));
+        assert!(read_csv_column(csv.as_bytes(), "address", 'é', &mut addresses).is_err());
+    }
+
+    #[test]
+    fn test_columns_in_first_seen_order() {
+        let mut records = input_records(&["a", "b"]);
+        records[0].insert("road".to_string(), json!("main st"));
+        records[1].insert("error".to_string(), json!("failed"));
+        records[1].insert("road".to_string(), json!("oak ave"));
+
+        assert_eq!(columns(&records), ["input", "road", "error"]);
+        assert_eq!(cell(None), "");
+        assert_eq!(cell(Some(&json!(["a", "b"]))), "a; b");
+        assert_eq!(cell(Some(&json!(0.5))), "0.5");
+    }
+
+    #[test]
+    fn test_write_records() {
+        let mut records = input_records(&["1 Main St", "x"]);
+        records[0].insert(
+            "expansions".to_string(),
+            json!(["1 main street", "1 main saint"]),
+        );
+        records[1].insert("error".to_string(), json!("failed, badly"));
+
+        assert_eq!(
+            rendered(&records, OutputFormat::Jsonl),
+            "{\"input\":\"1 Main St\",\"expansions\":[\"1 main street\",\"1 main saint\"]}\n\
+             {\"input\":\"x\",\"error\":\"failed, badly\"}\n"
+        );
+        assert_eq!(
+            rendered(&records, OutputFormat::Csv),
+            "input,expansions,error\n\
+             1 Main St,1 main street; 1 main saint,\n\
+             x,,\"failed, badly\"\n"
+        );
+        assert_eq!(
+            rendered(&records, OutputFormat::Table),
+            "input      expansions                   error\n\
+             ---------  ---------------------------  -------------\n\
+             1 Main St  1 main street; 1 main saint\n\
+             x                                       failed, badly\n"
+        );
+    }
+}
@@ -0,0 +1,476 @@
+//! Stable C ABI over the Rust API.
+//!
+//! The declarations live in `include/libpostal_rs.h`. `LibPostal`,
+//! [`AddressParser`] and [`AddressNormalizer`] are handed out as opaque
+//! pointers, every fallible call returns an [`LprStatus`], and the message of
+//! the last failure on the calling thread is available from [`lpr_last_error`].
+//! Results are JSON strings owned by the caller and released with
+//! [`lpr_string_free`].
+//!
+//! Panics never cross the boundary; they are reported as [`LprStatus::Panic`].
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use serde_json::json;
+
+use crate::batch::BatchMode;
+use crate::error::Error;
+use crate::normalizer::AddressNormalizer;
+use crate::parser::AddressParser;
+use crate::types::{Country, Language};
+use crate::{LibPostal, LibPostalConfig};
+
+/// Status codes returned by every fallible C function.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LprStatus {
+    /// The call succeeded
+    Ok = 0,
+    /// A null pointer, invalid UTF-8 or otherwise unusable argument was passed
+    InvalidArgument = 1,
+    /// libpostal failed to initialize ([`Error::InitializationFailed`])
+    Initialization = 2,
+    /// Data files are missing or unusable ([`Error::DataError`])
+    Data = 3,
+    /// Parsing failed ([`Error::ParseError`])
+    Parse = 4,
+    /// Normalization failed ([`Error::NormalizationError`])
+    Normalization = 5,
+    /// The libpostal C library reported an error ([`Error::FfiError`])
+    Ffi = 6,
+    /// An I/O operation failed ([`Error::IoError`])
+    Io = 7,
+    /// A data download failed
+    Network = 8,
+    /// Rust code panicked
+    Panic = 9,
+    /// Any other error
+    Other = 10,
+    /// The API needs a subsystem that was not enabled ([`Error::SubsystemDisabled`])
+    SubsystemDisabled = 11,
+}
+
+impl From<&Error> for LprStatus {
+    fn from(error: &Error) -> Self {
+        match error {
+            Error::InitializationFailed { .. } | Error::SetupFailed { .. } => {
+                LprStatus::Initialization
+            }
+            Error::SubsystemDisabled { .. } => LprStatus::SubsystemDisabled,
+            Error::DataError { .. } => LprStatus::Data,
+            Error::ParseError { .. } => LprStatus::Parse,
+            Error::NormalizationError { .. } => LprStatus::Normalization,
+            Error::FfiError { .. } => LprStatus::Ffi,
+            Error::IoError { .. } => LprStatus::Io,
+            #[cfg(feature = "runtime-data")]
+            Error::NetworkError { .. } => LprStatus::Network,
+            #[cfg(feature = "csv")]
+            Error::CsvError { .. } => LprStatus::Other,
+            #[cfg(feature = "arrow")]
+            Error::ArrowError { .. } => LprStatus::Other,
+        }
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// A failed call: the status to return and the message to record.
+struct CallError {
+    status: LprStatus,
+    message: String,
+}
+
+impl From<Error> for CallError {
+    fn from(error: Error) -> Self {
+        Self {
+            status: LprStatus::from(&error),
+            message: error.to_string(),
+        }
+    }
+}
+
+type CallResult<T> = std::result::Result<T, CallError>;
+
+fn invalid_argument(message: impl Into<String>) -> CallError {
+    CallError {
+        status: LprStatus::InvalidArgument,
+        message: message.into(),
+    }
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Run `call`, recording its error or panic as the thread's last error.
+fn guard(call: impl FnOnce() -> CallResult<()>) -> LprStatus {
+    match panic::catch_unwind(AssertUnwindSafe(call)) {
+        Ok(Ok(())) => {
+            LAST_ERROR.with(|last| *last.borrow_mut() = None);
+            LprStatus::Ok
+        }
+        Ok(Err(error)) => {
+            set_last_error(error.message);
+            error.status
+        }
+        Err(_) => {
+            set_last_error("panic inside libpostal-rs".to_string());
+            LprStatus::Panic
+        }
+    }
+}
+
+/// Borrow a required UTF-8 string argument.
+unsafe fn str_arg<'a>(value: *const c_char, name: &str) -> CallResult<&'a str> {
+    if value.is_null() {
+        return Err(invalid_argument(format!("{name} must not be null")));
+    }
+    // SAFETY: the caller guarantees `value` is a valid NUL-terminated string.
+    unsafe { CStr::from_ptr(value) }
+        .to_str()
+        .map_err(|_| invalid_argument(format!("{name} is not valid UTF-8")))
+}
+
+/// Borrow an optional UTF-8 string argument; null means absent.
+unsafe fn opt_str_arg<'a>(value: *const c_char, name: &str) -> CallResult<Option<&'a str>> {
+    if value.is_null() {
+        Ok(None)
+    } else {
+        // SAFETY: forwarded from the caller.
+        unsafe { str_arg(value, name) }.map(Some)
+    }
+}
+
+/// Borrow an array of `count` required strings.
+unsafe fn str_array_arg<'a>(
+    values: *const *const c_char,
+    count: usize,
+    name: &str,
+) -> CallResult<Vec<&'a str>> {
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+    if values.is_null() {
+        return Err(invalid_argument(format!("{name} must not be null")));
+    }
+    // SAFETY: the caller guarantees `values` points to `count` string pointers.
+    let values = unsafe { std::slice::from_raw_parts(values, count) };
+    values
+        .iter()
+        .enumerate()
+        // SAFETY: each element is a valid NUL-terminated string or null.
+        .map(|(i, value)| unsafe { str_arg(*value, &format!("{name}[{i}]")) })
+        .collect()
+}
+
+unsafe fn handle_arg<'a, T>(handle: *const T, name: &str) -> CallResult<&'a T> {
+    // SAFETY: the caller guarantees a non-null `handle` came from this library.
+    unsafe { handle.as_ref() }.ok_or_else(|| invalid_argument(format!("{name} must not be null")))
+}
+
+/// Store `value` in `out`, transferring ownership to the caller.
+unsafe fn write_out<T>(out: *mut *mut T, value: T) -> CallResult<()> {
+    if out.is_null() {
+        return Err(invalid_argument("output pointer must not be null"));
+    }
+    // SAFETY: `out` is non-null and the caller guarantees it is writable.
+    unsafe { *out = Box::into_raw(Box::new(value)) };
+    Ok(())
+}
+
+fn to_json(value: impl serde::Serialize) -> CallResult<serde_json::Value> {
+    serde_json::to_value(value).map_err(|e| CallError {
+        status: LprStatus::Other,
+        message: e.to_string(),
+    })
+}
+
+/// Store `json` in `out` as a caller-owned C string.
+unsafe fn write_json(out: *mut *mut c_char, json: serde_json::Value) -> CallResult<()> {
+    if out.is_null() {
+        return Err(invalid_argument("output pointer must not be null"));
+    }
+    // serde_json escapes NUL, so the serialized text never contains one.
+    let json = CString::new(json.to_string()).unwrap_or_default();
+    // SAFETY: `out` is non-null and the caller guarantees it is writable.
+    unsafe { *out = json.into_raw() };
+    Ok(())
+}
+
+/// Message of the last failed call on this thread, or null if the last call succeeded.
+///
+/// The pointer stays valid until the next call into this library on the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn lpr_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |m| m.as_ptr()))
+}
+
+/// Initialize libpostal and return a handle in `out`.
+///
+/// `data_dir` may be null to use the default data directory. With
+/// `auto_download`, missing data files are downloaded first.
+///
+/// # Safety
+///
+/// `data_dir` must be null or a valid NUL-terminated string, and `out` must be
+/// a valid pointer to write the handle to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lpr_init(
+    data_dir: *const c_char,
+    auto_download: bool,
+    out: *mut *mut LibPostal,
+) -> LprStatus {
+    guard(|| {
+        let data_dir = unsafe { opt_str_arg(data_dir, "data_dir") }?;
+
+        let mut builder = LibPostalConfig::builder().auto_download_data(auto_download);
+        if let Some(dir) = data_dir {
+            builder = builder.data_dir(dir);
+        }
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(Error::from)?;
+        let postal = runtime.block_on(LibPostal::with_config(builder.build()))?;
+
+        unsafe { write_out(out, postal) }
+    })
+}
+
+/// Release a handle returned by [`lpr_init`]. Null is ignored.
+///
+/// # Safety
+///
+/// `postal` must be null or a handle from [`lpr_init`] that has not been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lpr_free(postal: *mut LibPostal) {
+    if !postal.is_null() {
+        // SAFETY: the handle was created by `Box::into_raw` in `lpr_init`.
+        drop(unsafe { Box::from_raw(postal) });
+    }
+}
+
+/// Create a parser, optionally hinted with a language and country code.
+///
+/// # Safety
+///
+/// `postal` must be a live handle, `language` and `country` null or valid
+/// NUL-terminated strings, and `out` a valid pointer to write the parser to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lpr_parser_new(
+    postal: *const LibPostal,
+    language: *const c_char,
+    country: *const c_char,
+    out: *mut *mut AddressParser,
+) -> LprStatus {
+    guard(|| {
+        let postal = unsafe { handle_arg(postal, "postal") }?;
+        let mut parser = postal.parser();
+        if let Some(language) = unsafe { opt_str_arg(language, "language") }? {
+            parser = parser.with_language(Language::from_str(language));
+        }
+        if let Some(country) = unsafe { opt_str_arg(country, "country") }? {
+            parser = parser.with_country(Country::from_str(country));
+        }
+        unsafe { write_out(out, parser) }
+    })
+}
+
+/// Release a parser. Null is ignored.
+///
+/// # Safety
+///
+/// `parser` must be null or a parser from [`lpr_parser_new`] that has not been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lpr_parser_free(parser: *mut AddressParser) {
+    if !parser.is_null() {
+        // SAFETY: the parser was created by `Box::into_raw` in `lpr_parser_new`.
+        drop(unsafe { Box::from_raw(parser) });
+    }
+}
+
+/// Parse `address` and return its components as a JSON object in `out_json`.
+///
+/// # Safety
+///
+/// `parser` must be a live parser, `address` a valid NUL-terminated string and
+/// `out_json` a valid pointer. The result must be released with [`lpr_string_free`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lpr_parse(
+    parser: *const AddressParser,
+    address: *const c_char,
+    out_json: *mut *mut c_char,
+) -> LprStatus {
+    guard(|| {
+        let parser = unsafe { handle_arg(parser, "parser") }?;
+        let address = unsafe { str_arg(address, "address") }?;
+        let parsed = parser.parse(address)?;
+        unsafe { write_json(out_json, to_json(parsed)?) }
+    })
+}
+
+/// Parse `count` addresses and return a JSON batch result in `out_json`.
+///
+/// The result is `{"results": [...], "errors": [...]}`: `results` holds one
+/// object per input, or `null` where parsing failed, and `errors` holds
+/// `{"index", "error"}` entries for the failures. Per-item failures do not
+/// make the call itself fail.
+///
+/// # Safety
+///
+/// `parser` must be a live parser, `addresses` must point to `count` valid
+/// NUL-terminated strings and `out_json` must be a valid pointer. The result
+/// must be released with [`lpr_string_free`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lpr_parse_batch(
+    parser: *const AddressParser,
+    addresses: *const *const c_char,
+    count: usize,
+    out_json: *mut *mut c_char,
+) -> LprStatus {
+    guard(|| {
+        let parser = unsafe { handle_arg(parser, "parser") }?;
+        let addresses = unsafe { str_array_arg(addresses, count, "addresses") }?;
+        let report = parser.parse_batch_report(&addresses, BatchMode::Collect);
+
+        let mut results = vec![serde_json::Value::Null; report.total];
+        for (index, parsed) in report.successes {
+            results[index] = to_json(parsed)?;
+        }
+        let errors: Vec<_> = report
+            .failures
+            .iter()
+            .map(|f| json!({ "index": f.index, "error": f.error.to_string() }))
+            .collect();
+
+        unsafe { write_json(out_json, json!({ "results": results, "errors": errors })) }
+    })
+}
+
+/// Create a normalizer for `language_count` language codes (none for automatic detection).
+///
+/// # Safety
+///
+/// `postal` must be a live handle, `languages` must point to `language_count`
+/// valid NUL-terminated strings (it may be null when the count is zero) and
+/// `out` must be a valid pointer to write the normalizer to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lpr_normalizer_new(
+    postal: *const LibPostal,
+    languages: *const *const c_char,
+    language_count: usize,
+    out: *mut *mut AddressNormalizer,
+) -> LprStatus {
+    guard(|| {
+        let postal = unsafe { handle_arg(postal, "postal") }?;
+        let languages = unsafe { str_array_arg(languages, language_count, "languages") }?;
+
+        let mut normalizer = postal.normalizer();
+        if !languages.is_empty() {
+            let languages: Vec<Language> =
+                languages.iter().map(|l| Language::from_str(l)).collect();
+            normalizer = normalizer.with_languages(&languages);
+        }
+        unsafe { write_out(out, normalizer) }
+    })
+}
+
+/// Release a normalizer. Null is ignored.
+///
+/// # Safety
+///
+/// `normalizer` must be null or a normalizer from [`lpr_normalizer_new`] that
+/// has not been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lpr_normalizer_free(normalizer: *mut AddressNormalizer) {
+    if !normalizer.is_null() {
+        // SAFETY: the normalizer was created by `Box::into_raw` in `lpr_normalizer_new`.
+        drop(unsafe { Box::from_raw(normalizer) });
+    }
+}
+
+/// Expand `address` and return its normalized forms as a JSON array of strings.
+///
+/// # Safety
+///
+/// `normalizer` must be a live normalizer, `address` a valid NUL-terminated
+/// string and `out_json` a valid pointer. The result must be released with
+/// [`lpr_string_free`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lpr_expand(
+    normalizer: *const AddressNormalizer,
+    address: *const c_char,
+    out_json: *mut *mut c_char,
+) -> LprStatus {
+    guard(|| {
+        let normalizer = unsafe { handle_arg(normalizer, "normalizer") }?;
+        let address = unsafe { str_arg(address, "address") }?;
+        let normalized = normalizer.normalize(address)?;
+        unsafe { write_json(out_json, json!(normalized.expansions)) }
+    })
+}
+
+/// Release a string returned by this library. Null is ignored.
+///
+/// # Safety
+///
+/// `value` must be null or a string returned by this library that has not been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lpr_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        // SAFETY: the string was created by `CString::into_raw` in `write_json`.
+        drop(unsafe { CString::from_raw(value) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_error() -> Option<String> {
+        let message = lpr_last_error();
+        (!message.is_null()).then(|| {
+            unsafe { CStr::from_ptr(message) }
+                .to_string_lossy()
+                .into_owned()
+        })
+    }
+
+    #[test]
+    fn test_null_arguments() {
+        let mut json = ptr::null_mut();
+        let status = unsafe { lpr_parse(ptr::null(), c"123 Main St".as_ptr(), &mut json) };
+
+        assert_eq!(status, LprStatus::InvalidArgument);
+        assert!(json.is_null());
+        assert_eq!(last_error().as_deref(), Some("parser must not be null"));
+    }
+
+    #[test]
+    fn test_error_status_mapping() {
+        assert_eq!(
+            LprStatus::from(&Error::parse_error("bad")),
+            LprStatus::Parse
+        );
+        assert_eq!(
+            LprStatus::from(&Error::data_error("missing")),
+            LprStatus::Data
+        );
+    }
+
+    #[test]
+    fn test_free_null_is_noop() {
+        unsafe {
+            lpr_free(ptr::null_mut());
+            lpr_parser_free(ptr::null_mut());
+            lpr_normalizer_free(ptr::null_mut());
+            lpr_string_free(ptr::null_mut());
+        }
+    }
+}
@@ -0,0 +1,318 @@
+//! CSV processing: parse an address column and append the parsed components.
+//!
+//! The output keeps every original column and adds one column per
+//! [`ParsedAddress`] field, so a CSV export can be enriched in one pass.
+//!
+//! ```rust,no_run
+//! use libpostal_rs::csv::CsvProcessor;
+//! use libpostal_rs::LibPostal;
+//!
+//! # async fn run() -> libpostal_rs::Result<()> {
+//! let postal = LibPostal::new().await?;
+//! let summary = CsvProcessor::new(postal.parser())
+//!     .with_address_columns(&["street", "city", "zip"])
+//!     .with_prefix("addr_")
+//!     .process_file("customers.csv", "customers_parsed.csv")?;
+//!
+//! println!("parsed {} of {} rows", summary.parsed, summary.rows);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io::{Read, Write};
+use std::path::Path;
+
+use crate::batch::BatchMode;
+use crate::error::{Error, Result};
+use crate::parser::{AddressParser, ParsedAddress};
+
+/// Default number of rows parsed per batch.
+const DEFAULT_BATCH_SIZE: usize = 1024;
+
+/// Reads a CSV, parses its address column(s) and writes the enriched CSV.
+#[derive(Debug)]
+pub struct CsvProcessor {
+    parser: AddressParser,
+    address_columns: Vec<String>,
+    separator: String,
+    prefix: String,
+    error_column: Option<String>,
+    delimiter: u8,
+    batch_size: usize,
+}
+
+/// Row counts from a finished CSV run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CsvSummary {
+    /// Number of data rows read
+    pub rows: usize,
+    /// Number of rows parsed successfully
+    pub parsed: usize,
+    /// Number of rows whose address could not be parsed
+    pub failed: usize,
+}
+
+impl CsvProcessor {
+    /// Create a processor that parses the `address` column with `parser`.
+    ///
+    /// Failed rows are reported in a `parse_error` column and parsed fields
+    /// are written without a prefix.
+    pub fn new(parser: AddressParser) -> Self {
+        Self {
+            parser,
+            address_columns: vec!["address".to_string()],
+            separator: ", ".to_string(),
+            prefix: String::new(),
+            error_column: Some("parse_error".to_string()),
+            delimiter: b',',
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// Parse the address held in a single column.
+    pub fn with_address_column(mut self, column: &str) -> Self {
+        self.address_columns = vec![column.to_string()];
+        self
+    }
+
+    /// Build the address by joining several columns, in order.
+    ///
+    /// Empty cells are skipped so a missing unit or zip doesn't leave stray separators.
+    pub fn with_address_columns(mut self, columns: &[&str]) -> Self {
+        self.address_columns = columns.iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    /// Set the separator used when joining several address columns (default `", "`).
+    pub fn with_separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Prefix every output column name added for a parsed field.
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    /// Set the column that receives per-row parse errors.
+    ///
+    /// With `None`, the first row that fails to parse aborts the run.
+    pub fn with_error_column(mut self, column: Option<&str>) -> Self {
+        self.error_column = column.map(str::to_string);
+        self
+    }
+
+    /// Set the field delimiter for both input and output (default `b','`).
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Set how many rows are parsed per batch.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Process `input` and write the result to `output`.
+    pub fn process_file<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        input: P,
+        output: Q,
+    ) -> Result<CsvSummary> {
+        let reader = std::fs::File::open(input.as_ref()).map_err(|e| {
+            Error::csv_error(format!("Failed to open {}: {e}", input.as_ref().display()))
+        })?;
+        let writer = std::fs::File::create(output.as_ref()).map_err(|e| {
+            Error::csv_error(format!(
+                "Failed to create {}: {e}",
+                output.as_ref().display()
+            ))
+        })?;
+        self.process(reader, std::io::BufWriter::new(writer))
+    }
+
+    /// Process CSV data from `reader` and write the result to `writer`.
+    ///
+    /// The input must have a header row.
+    pub fn process<R: Read, W: Write>(&self, reader: R, writer: W) -> Result<CsvSummary> {
+        let mut reader = ::csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .from_reader(reader);
+        let mut writer = ::csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .from_writer(writer);
+
+        let headers = reader.headers().map_err(csv_error)?.clone();
+        let indices = self.address_indices(&headers)?;
+        writer
+            .write_record(self.output_headers(&headers))
+            .map_err(csv_error)?;
+
+        let mode = if self.error_column.is_some() {
+            BatchMode::Collect
+        } else {
+            BatchMode::FailFast
+        };
+
+        let mut summary = CsvSummary::default();
+        let mut rows = Vec::with_capacity(self.batch_size);
+        let mut records = reader.into_records();
+        loop {
+            rows.clear();
+            for record in records.by_ref().take(self.batch_size) {
+                rows.push(record.map_err(csv_error)?);
+            }
+            if rows.is_empty() {
+                break;
+            }
+
+            let addresses: Vec<String> = rows
+                .iter()
+                .map(|row| self.build_address(row, &indices))
+                .collect();
+            let addresses: Vec<&str> = addresses.iter().map(String::as_str).collect();
+            let report = self.parser.parse_batch_report(&addresses, mode);
+
+            if mode == BatchMode::FailFast
+                && let Some(failure) = report.failures.first()
+            {
+                return Err(Error::csv_error(format!(
+                    "Row {}: {}",
+                    summary.rows + failure.index + 1,
+                    failure.error
+                )));
+            }
+
+            let mut parsed: Vec<Option<ParsedAddress>> = (0..rows.len()).map(|_| None).collect();
+            for (index, value) in report.successes {
+                parsed[index] = Some(value);
+            }
+            let mut errors: Vec<Option<String>> = vec![None; rows.len()];
+            for failure in report.failures {
+                errors[failure.index] = Some(failure.error.to_string());
+            }
+
+            for ((row, parsed), error) in rows.iter().zip(&parsed).zip(&errors) {
+                writer
+                    .write_record(self.output_row(row, parsed.as_ref(), error.as_deref()))
+                    .map_err(csv_error)?;
+            }
+
+            summary.rows += rows.len();
+            summary.failed += errors.iter().filter(|e| e.is_some()).count();
+        }
+        summary.parsed = summary.rows - summary.failed;
+
+        writer.flush()?;
+        Ok(summary)
+    }
+
+    /// Locate the configured address columns in the header row.
+    fn address_indices(&self, headers: &::csv::StringRecord) -> Result<Vec<usize>> {
+        self.address_columns
+            .iter()
+            .map(|column| {
+                headers
+                    .iter()
+                    .position(|header| header == column)
+                    .ok_or_else(|| {
+                        Error::csv_error(format!("CSV input has no column named {column:?}"))
+                    })
+            })
+            .collect()
+    }
+
+    /// Original headers followed by one column per parsed field and the error column.
+    fn output_headers(&self, headers: &::csv::StringRecord) -> Vec<String> {
+        let mut output: Vec<String> = headers.iter().map(str::to_string).collect();
+        output.extend(
+            ParsedAddress::LABELS
+                .iter()
+                .chain(["other"].iter())
+                .map(|label| format!("{}{label}", self.prefix)),
+        );
+        if let Some(column) = &self.error_column {
+            output.push(column.clone());
+        }
+        output
+    }
+
+    fn output_row(
+        &self,
+        row: &::csv::StringRecord,
+        parsed: Option<&ParsedAddress>,
+        error: Option<&str>,
+    ) -> Vec<String> {
+        let mut output: Vec<String> = row.iter().map(str::to_string).collect();
+        for label in ParsedAddress::LABELS {
+            output.push(
+                parsed
+                    .and_then(|p| p.get(label))
+                    .unwrap_or_default()
+                    .to_string(),
+            );
+        }
+        output.push(parsed.map(|p| p.other.join("; ")).unwrap_or_default());
+        if self.error_column.is_some() {
+            output.push(error.unwrap_or_default().to_string());
+        }
+        output
+    }
+
+    fn build_address(&self, row: &::csv::StringRecord, indices: &[usize]) -> String {
+        indices
+            .iter()
+            .filter_map(|&i| row.get(i))
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .collect::<Vec<_>>()
+            .join(&self.separator)
+    }
+}
+
+fn csv_error(error: ::csv::Error) -> Error {
+    Error::csv_error(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers() -> ::csv::StringRecord {
+        ::csv::StringRecord::from(vec!["id", "street", "city", "zip"])
+    }
+
+    #[test]
+    fn test_build_address_from_columns() {
+        let processor = CsvProcessor::new(AddressParser::new())
+            .with_address_columns(&["street", "city", "zip"]);
+        let indices = processor.address_indices(&headers()).unwrap();
+        let row = ::csv::StringRecord::from(vec!["1", "123 Main St", " ", "10001"]);
+
+        assert_eq!(indices, vec![1, 2, 3]);
+        assert_eq!(
+            processor.build_address(&row, &indices),
+            "123 Main St, 10001"
+        );
+    }
+
+    #[test]
+    fn test_output_headers() {
+        let processor = CsvProcessor::new(AddressParser::new()).with_prefix("addr_");
+        let output = processor.output_headers(&headers());
+
+        assert_eq!(&output[..4], &["id", "street", "city", "zip"]);
+        assert_eq!(output[4], "addr_house_number");
+        assert_eq!(output.last().map(String::as_str), Some("parse_error"));
+        assert_eq!(output.len(), 4 + ParsedAddress::LABELS.len() + 2);
+    }
+
+    #[test]
+    fn test_missing_column() {
+        let processor = CsvProcessor::new(AddressParser::new()).with_address_column("address");
+        let result = processor.process("id,street\n1,Main St\n".as_bytes(), Vec::new());
+        assert!(matches!(result, Err(Error::CsvError { .. })));
+    }
+}
@@ -1,14 +1,30 @@
 //! Data file management for libpostal.
 
 use crate::error::Result;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Duration;
 
+#[cfg_attr(not(feature = "runtime-data"), allow(dead_code))]
+mod generation;
+mod integrity;
+mod lock;
+mod release;
+
+pub use integrity::{ChecksumManifest, FileCheck, FileStatus, VerificationReport, sha256_file};
+use lock::DataLock;
+pub use release::{ComponentUpdate, ReleaseManifest};
+
 #[cfg(feature = "runtime-data")]
 use std::io::Write;
 
 #[cfg(feature = "runtime-data")]
-use futures::future;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[cfg(feature = "runtime-data")]
+use std::time::Instant;
 
 /// Essential libpostal data files that must be present for the library to function.
 const REQUIRED_DATA_FILES: &[&str] = &[
@@ -28,6 +44,18 @@ const CHUNK_SIZE: usize = 64 * 1024 * 1024;
 /// Default number of parallel download workers.
 const DEFAULT_NUM_WORKERS: usize = 12;
 
+/// Backoff before the first retry of a failed download; doubles per retry.
+#[cfg(feature = "runtime-data")]
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Upper bound on the backoff between download retries.
+#[cfg(feature = "runtime-data")]
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Shortest time between two progress reports while a component downloads.
+#[cfg(feature = "runtime-data")]
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
 /// Component file information.
 const LIBPOSTAL_DATA_FILE_CHUNKS: usize = 1;
 const LIBPOSTAL_PARSER_MODEL_CHUNKS: usize = 12;
@@ -43,13 +71,31 @@ const LIBPOSTAL_LANG_CLASS_FILE: &str = "language_classifier.tar.gz";
 
 const LIBPOSTAL_BASE_URL: &str = "https://github.com/openvenues/libpostal/releases/download";
 
+/// Release of the Senzing-trained models, introduced with libpostal v1.1.
+const SENZING_MODEL_RELEASE: &str = "v1.1.0";
+#[cfg(feature = "runtime-data")]
+const SENZING_BASE_URL: &str = "https://public-read-libpostal-data.s3.amazonaws.com";
+/// Prefix of the installed version of a Senzing component, e.g. "senzing-v1.1.0".
+const SENZING_VERSION_PREFIX: &str = "senzing-";
+
+/// Directory the `bundled-data` build places the data in, next to the binaries.
+#[cfg(feature = "bundled-data")]
+const BUNDLED_DATA_DIR_NAME: &str = "libpostal-data";
+
 /// Module directories for data organization.
 const BASIC_MODULE_DIRS: &[&str] = &["address_expansions", "numex", "transliteration"];
 const PARSER_MODULE_DIR: &str = "address_parser";
 const LANGUAGE_CLASSIFIER_MODULE_DIR: &str = "language_classifier";
 
+/// Components that are installed individually, in install order.
+const INSTALLED_COMPONENTS: &[DataComponent] = &[
+    DataComponent::Base,
+    DataComponent::Parser,
+    DataComponent::LanguageClassifier,
+];
+
 /// Data component types for libpostal.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DataComponent {
     /// Base data (address_expansions, numex, transliteration)
     Base,
@@ -61,11 +107,95 @@ pub enum DataComponent {
     All,
 }
 
+impl fmt::Display for DataComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DataComponent::Base => "base",
+            DataComponent::Parser => "parser",
+            DataComponent::LanguageClassifier => "language_classifier",
+            DataComponent::All => "all",
+        })
+    }
+}
+
+/// The installed component named `name`, as written by its `Display` impl.
+fn parse_component(name: &str) -> Option<DataComponent> {
+    INSTALLED_COMPONENTS
+        .iter()
+        .copied()
+        .find(|component| component.to_string() == name)
+}
+
+/// Set of libpostal models to install.
+///
+/// The address parser comes in two trainings, each published with its own
+/// base data and language classifier. Switching models reinstalls all three
+/// components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ParserModel {
+    /// The models trained by the libpostal project
+    #[default]
+    Default,
+    /// The models trained by Senzing, published with libpostal v1.1
+    Senzing,
+}
+
+impl ParserModel {
+    /// Information about a component of this model set.
+    pub fn component_info(self, component: DataComponent) -> ComponentInfo {
+        let mut info = get_component_info(component);
+        if self == ParserModel::Senzing && component != DataComponent::All {
+            info.version = format!("{SENZING_VERSION_PREFIX}{SENZING_MODEL_RELEASE}");
+            info.release = SENZING_MODEL_RELEASE.to_string();
+            info.num_chunks = 1;
+        }
+        info
+    }
+
+    /// The model set a component's installed version belongs to.
+    ///
+    /// Versions written before model sets existed belong to the default one.
+    pub fn from_version(version: &str) -> Self {
+        if version.starts_with(SENZING_VERSION_PREFIX) {
+            ParserModel::Senzing
+        } else {
+            ParserModel::Default
+        }
+    }
+
+    /// Release a version of a component is published under, e.g. "v1.1.0" for "senzing-v1.1.0".
+    fn release_of(version: &str) -> &str {
+        version
+            .strip_prefix(SENZING_VERSION_PREFIX)
+            .unwrap_or(version)
+    }
+
+    /// Where this model set's archives are published, as `{url}/{release}/{filename}`.
+    #[cfg(feature = "runtime-data")]
+    fn release_base_url(self) -> &'static str {
+        match self {
+            ParserModel::Default => LIBPOSTAL_BASE_URL,
+            ParserModel::Senzing => SENZING_BASE_URL,
+        }
+    }
+}
+
+impl fmt::Display for ParserModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParserModel::Default => "default",
+            ParserModel::Senzing => "senzing",
+        })
+    }
+}
+
 /// Information about a data component.
 #[derive(Debug, Clone)]
 pub struct ComponentInfo {
     /// Component version
     pub version: String,
+    /// Release the archive is published under
+    pub release: String,
     /// Number of chunks for multipart download
     pub num_chunks: usize,
     /// Archive filename
@@ -76,14 +206,27 @@ pub struct ComponentInfo {
     pub subdirs: Vec<String>,
 }
 
+impl ComponentInfo {
+    /// This component at another version of the same model set.
+    fn at_version(mut self, version: &str) -> Self {
+        self.release = ParserModel::release_of(version).to_string();
+        self.version = version.to_string();
+        self
+    }
+}
+
 /// Download progress information.
 #[derive(Debug, Clone)]
 pub struct DownloadProgress {
-    /// Total bytes to download
+    /// Component being installed
+    pub component: DataComponent,
+    /// What is being done to the component
+    pub stage: DownloadStage,
+    /// Total bytes to download, 0 until the server reports it
     pub total_bytes: u64,
-    /// Bytes downloaded so far
+    /// Bytes downloaded so far, including parts resumed from an earlier run
     pub downloaded_bytes: u64,
-    /// Current chunk being downloaded
+    /// Chunk that last received data, starting at 1
     pub current_chunk: usize,
     /// Total number of chunks
     pub total_chunks: usize,
@@ -91,6 +234,81 @@ pub struct DownloadProgress {
     pub speed_bps: u64,
 }
 
+/// Step of installing a data component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadStage {
+    /// The archive is being downloaded
+    Downloading,
+    /// The downloaded archive is being checked against its checksum
+    Verifying,
+    /// The archive is being unpacked into the data directory
+    Extracting,
+    /// The component is installed and up to date
+    Complete,
+}
+
+impl fmt::Display for DownloadStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DownloadStage::Downloading => "downloading",
+            DownloadStage::Verifying => "verifying",
+            DownloadStage::Extracting => "extracting",
+            DownloadStage::Complete => "complete",
+        })
+    }
+}
+
+/// Receives [`DownloadProgress`] updates while data is downloaded.
+///
+/// The callback runs on the download tasks, so it should return quickly. It
+/// is called when a component changes [`DownloadStage`] and at most every
+/// 100ms while bytes arrive.
+///
+/// ```rust,no_run
+/// use libpostal_rs::data::{DataManager, ProgressCallback};
+///
+/// # async fn run() -> libpostal_rs::Result<()> {
+/// let manager = DataManager::new().with_progress(ProgressCallback::new(|progress| {
+///     eprintln!("{} {}: {} bytes", progress.component, progress.stage, progress.downloaded_bytes);
+/// }));
+/// manager.ensure_data().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ProgressCallback(Arc<dyn Fn(&DownloadProgress) + Send + Sync>);
+
+impl ProgressCallback {
+    /// Wrap a closure that receives each update.
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Fn(&DownloadProgress) + Send + Sync + 'static,
+    {
+        Self(Arc::new(callback))
+    }
+
+    /// A callback that publishes the latest update on a `tokio::sync::watch` channel.
+    #[cfg(feature = "runtime-data")]
+    pub fn watch() -> (Self, tokio::sync::watch::Receiver<Option<DownloadProgress>>) {
+        let (sender, receiver) = tokio::sync::watch::channel(None);
+        let callback = Self::new(move |progress| {
+            sender.send_replace(Some(progress.clone()));
+        });
+        (callback, receiver)
+    }
+
+    #[cfg_attr(not(feature = "runtime-data"), allow(dead_code))]
+    pub(crate) fn emit(&self, progress: &DownloadProgress) {
+        (self.0)(progress)
+    }
+}
+
+impl fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressCallback(..)")
+    }
+}
+
 /// GitHub release asset information.
 #[derive(Debug, Clone)]
 pub struct ReleaseAsset {
@@ -102,11 +320,14 @@ pub struct ReleaseAsset {
     pub size: u64,
 }
 
-/// Get component information for a data component.
+/// Get component information for a data component of the default model set.
+///
+/// Use [`ParserModel::component_info`] for another model set.
 pub fn get_component_info(component: DataComponent) -> ComponentInfo {
     match component {
         DataComponent::Base => ComponentInfo {
             version: LIBPOSTAL_DATA_FILE_LATEST_VERSION.to_string(),
+            release: LIBPOSTAL_DATA_FILE_LATEST_VERSION.to_string(),
             num_chunks: LIBPOSTAL_DATA_FILE_CHUNKS,
             filename: LIBPOSTAL_DATA_FILE.to_string(),
             name: "data file".to_string(),
@@ -114,6 +335,7 @@ pub fn get_component_info(component: DataComponent) -> ComponentInfo {
         },
         DataComponent::Parser => ComponentInfo {
             version: LIBPOSTAL_PARSER_MODEL_LATEST_VERSION.to_string(),
+            release: LIBPOSTAL_PARSER_MODEL_LATEST_VERSION.to_string(),
             num_chunks: LIBPOSTAL_PARSER_MODEL_CHUNKS,
             filename: LIBPOSTAL_PARSER_FILE.to_string(),
             name: "parser data file".to_string(),
@@ -121,6 +343,7 @@ pub fn get_component_info(component: DataComponent) -> ComponentInfo {
         },
         DataComponent::LanguageClassifier => ComponentInfo {
             version: LIBPOSTAL_LANG_CLASS_MODEL_LATEST_VERSION.to_string(),
+            release: LIBPOSTAL_LANG_CLASS_MODEL_LATEST_VERSION.to_string(),
             num_chunks: LIBPOSTAL_LANG_CLASS_MODEL_CHUNKS,
             filename: LIBPOSTAL_LANG_CLASS_FILE.to_string(),
             name: "language classifier data file".to_string(),
@@ -128,6 +351,7 @@ pub fn get_component_info(component: DataComponent) -> ComponentInfo {
         },
         DataComponent::All => ComponentInfo {
             version: "all".to_string(),
+            release: "all".to_string(),
             num_chunks: 0, // Special case handled separately
             filename: "all".to_string(),
             name: "all components".to_string(),
@@ -136,15 +360,51 @@ pub fn get_component_info(component: DataComponent) -> ComponentInfo {
     }
 }
 
-/// Get version file path for a component.
-fn get_version_file_path(component: DataComponent, data_dir: &Path) -> PathBuf {
-    let filename = match component {
+/// Name of the file holding a component's installed version.
+fn version_file_name(component: DataComponent) -> &'static str {
+    match component {
         DataComponent::Base => "base_data_file_version",
-        DataComponent::Parser => "parser_model_file_version", 
+        DataComponent::Parser => "parser_model_file_version",
         DataComponent::LanguageClassifier => "language_classifier_model_file_version",
         DataComponent::All => "data_version",
-    };
-    data_dir.join(filename)
+    }
+}
+
+/// Name of the file holding the checksums recorded when a component was installed.
+fn checksums_file_name(component: DataComponent) -> &'static str {
+    match component {
+        DataComponent::Base => "base_data_file_checksums",
+        DataComponent::Parser => "parser_model_file_checksums",
+        DataComponent::LanguageClassifier => "language_classifier_model_file_checksums",
+        DataComponent::All => "data_checksums",
+    }
+}
+
+/// Get version file path for a component.
+fn get_version_file_path(component: DataComponent, data_dir: &Path) -> PathBuf {
+    data_dir.join(version_file_name(component))
+}
+
+/// The model set of the data in `data_dir`, judged by the parser's version file.
+pub(crate) fn installed_parser_model(data_dir: &Path) -> ParserModel {
+    std::fs::read_to_string(get_version_file_path(DataComponent::Parser, data_dir))
+        .map_or(ParserModel::Default, |version| {
+            ParserModel::from_version(version.trim())
+        })
+}
+
+/// Get the path of the checksums recorded when a component was installed.
+fn get_checksums_file_path(component: DataComponent, data_dir: &Path) -> PathBuf {
+    data_dir.join(checksums_file_name(component))
+}
+
+/// Required data files that belong to a component.
+fn required_files(component: DataComponent) -> impl Iterator<Item = &'static str> {
+    let subdirs = get_component_info(component).subdirs;
+    REQUIRED_DATA_FILES
+        .iter()
+        .copied()
+        .filter(move |file| subdirs.iter().any(|dir| Path::new(file).starts_with(dir)))
 }
 
 /// Data file manager for libpostal.
@@ -178,11 +438,131 @@ impl DataManager {
         }
     }
 
+    /// Report download progress to `callback`.
+    pub fn with_progress(mut self, callback: ProgressCallback) -> Self {
+        self.config.progress = Some(callback);
+        self
+    }
+
     /// Get the data directory path.
     pub fn data_dir(&self) -> &Path {
         &self.data_dir
     }
 
+    /// Get the directory libpostal reads its data from.
+    ///
+    /// Installs go into a new generation directory under the data directory
+    /// and switch to it atomically; this is the current generation, or the
+    /// data directory itself if it was filled before generations were used.
+    pub fn active_data_dir(&self) -> PathBuf {
+        generation::active_dir(&self.data_dir)
+    }
+
+    /// Clean up after an install that was interrupted.
+    ///
+    /// Removes leftover staging directories and old generations. Called
+    /// before every install, and by [`ensure_data`](Self::ensure_data) when the
+    /// data directory is writable.
+    ///
+    /// Waits for an install running in another process or thread to finish.
+    pub fn recover(&self) -> Result<()> {
+        if !self.data_dir.is_dir() {
+            return Ok(());
+        }
+        let _lock = self.lock()?;
+        self.recover_locked()
+    }
+
+    /// [`recover`](Self::recover) for a caller that holds the lock.
+    fn recover_locked(&self) -> Result<()> {
+        generation::recover(&self.data_dir).map_err(|e| {
+            Self::data_error(format!(
+                "Failed to recover data directory {}: {e}",
+                self.data_dir.display()
+            ))
+        })
+    }
+
+    /// [`recover`](Self::recover) if the data directory is writable and
+    /// nobody holds the lock; otherwise leave it to the next install.
+    #[cfg(feature = "runtime-data")]
+    fn try_recover(&self) -> Result<()> {
+        match DataLock::try_acquire(&self.data_dir) {
+            Ok(Some(_lock)) => self.recover_locked(),
+            // Read-only, or an install is running and recovers itself
+            Ok(None) | Err(_) => Ok(()),
+        }
+    }
+
+    /// Take the lock on the data directory, creating the directory if needed.
+    ///
+    /// Waits up to `DataConfig::lock_timeout_seconds` for the current holder.
+    fn lock(&self) -> Result<DataLock> {
+        Self::create_dir_all(&self.data_dir)?;
+        let lock = DataLock::acquire(&self.data_dir, self.lock_timeout())
+            .map_err(|e| self.lock_error(e))?;
+        lock.ok_or_else(|| self.lock_timeout_error())
+    }
+
+    /// Like [`lock`](Self::lock), without blocking the async runtime while waiting.
+    #[cfg(feature = "runtime-data")]
+    async fn lock_async(&self) -> Result<DataLock> {
+        Self::create_dir_all(&self.data_dir)?;
+        let lock = DataLock::acquire_async(&self.data_dir, self.lock_timeout())
+            .await
+            .map_err(|e| self.lock_error(e))?;
+        lock.ok_or_else(|| self.lock_timeout_error())
+    }
+
+    fn lock_timeout(&self) -> Duration {
+        Duration::from_secs(self.config.lock_timeout_seconds)
+    }
+
+    fn lock_error(&self, e: std::io::Error) -> crate::error::Error {
+        Self::data_error(format!(
+            "Failed to lock data directory {}: {e}",
+            self.data_dir.display()
+        ))
+    }
+
+    fn lock_timeout_error(&self) -> crate::error::Error {
+        Self::data_error(format!(
+            "Timed out after {}s waiting for another process to finish with data directory {}",
+            self.config.lock_timeout_seconds,
+            self.data_dir.display()
+        ))
+    }
+
+    /// Information about a component of the configured model set, at its
+    /// pinned version if it has one.
+    pub fn component_info(&self, component: DataComponent) -> ComponentInfo {
+        let info = self.config.parser_model.component_info(component);
+        match self.config.pinned_versions.get(&component) {
+            Some(version) => info.at_version(version),
+            None => info,
+        }
+    }
+
+    /// Installed version of each component that has a version file.
+    ///
+    /// Use this to report which data a deployment runs.
+    pub fn installed_versions(&self) -> Result<HashMap<DataComponent, String>> {
+        let mut versions = HashMap::new();
+        for &component in INSTALLED_COMPONENTS {
+            if let Some(version) = self.check_component_version(component)? {
+                versions.insert(component, version);
+            }
+        }
+        Ok(versions)
+    }
+
+    /// The model set of the installed data.
+    ///
+    /// Data installed before model sets existed belongs to the default one.
+    pub fn installed_parser_model(&self) -> ParserModel {
+        installed_parser_model(&self.active_data_dir())
+    }
+
     /// Get the configuration.
     pub fn config(&self) -> &DataConfig {
         &self.config
@@ -196,20 +576,24 @@ impl DataManager {
     /// Helper to create directories with error handling
     fn create_dir_all<P: AsRef<Path>>(path: P) -> Result<()> {
         std::fs::create_dir_all(path.as_ref()).map_err(|e| {
-            Self::data_error(format!("Failed to create directory {}: {e}", path.as_ref().display()))
+            Self::data_error(format!(
+                "Failed to create directory {}: {e}",
+                path.as_ref().display()
+            ))
         })
     }
 
     /// Check if required data files are present.
     pub fn is_data_available(&self) -> bool {
-        if !self.data_dir.exists() {
+        let active = self.active_data_dir();
+        if !active.exists() {
             return false;
         }
 
         // Check for essential libpostal data files
-        REQUIRED_DATA_FILES.iter().all(|file| {
-            self.data_dir.join(file).exists()
-        })
+        REQUIRED_DATA_FILES
+            .iter()
+            .all(|file| active.join(file).exists())
     }
 
     /// Download required data files.
@@ -219,7 +603,6 @@ impl DataManager {
         self.ensure_data().await
     }
 
-
     /// Get the size of downloaded data.
     pub fn data_size(&self) -> Result<u64> {
         if !self.data_dir.exists() {
@@ -259,16 +642,19 @@ impl DataManager {
         Ok(())
     }
 
-    /// Verify integrity of data files.
+    /// Check that the required data files exist and are non-empty.
+    ///
+    /// This is a quick check; use [`verify_integrity`](Self::verify_integrity)
+    /// to compare checksums.
     pub fn verify_data(&self) -> Result<()> {
         if !self.is_data_available() {
             return Err(crate::error::Error::data_error("Data files not found"));
         }
 
-        // Check that files exist and are non-empty
-        // Future enhancement: implement SHA256 checksum verification
+        // Only checks that files exist and are non-empty; see verify_integrity
+        let active = self.active_data_dir();
         for file in REQUIRED_DATA_FILES {
-            let path = self.data_dir.join(file);
+            let path = active.join(file);
             if !path.exists() {
                 return Err(crate::error::Error::data_error(format!(
                     "Missing data file: {file}"
@@ -289,10 +675,142 @@ impl DataManager {
         Ok(())
     }
 
-    /// Check the version of a specific component.
+    /// Verify the checksums of every installed data file.
+    ///
+    /// Each component's files are checked against the checksum manifest for
+    /// its installed version, falling back to the checksums recorded when it
+    /// was installed. Required files without a known checksum are only
+    /// checked for presence. The report lists every file; use
+    /// [`VerificationReport::into_result`] to turn corruption into an error.
+    pub fn verify_integrity(&self) -> Result<VerificationReport> {
+        let manifest = self.checksum_manifest()?;
+        let active = self.active_data_dir();
+        let mut report = VerificationReport::default();
+
+        for &component in INSTALLED_COMPONENTS {
+            let info = self.component_info(component);
+            let version = self
+                .check_component_version(component)?
+                .unwrap_or(info.version);
+
+            let mut expected: BTreeMap<String, Option<String>> = BTreeMap::new();
+            for file in required_files(component) {
+                expected.insert(file.to_string(), None);
+            }
+            for (path, sha256) in self.recorded_checksums(component)? {
+                expected.insert(path, Some(sha256));
+            }
+            for (path, sha256) in manifest.files(component, &version) {
+                if path != info.filename {
+                    expected.insert(path.to_string(), Some(sha256.to_string()));
+                }
+            }
+
+            for (path, sha256) in expected {
+                let status = integrity::check_file(&active.join(&path), sha256.as_deref())?;
+                report.files.push(FileCheck {
+                    component,
+                    path: PathBuf::from(path),
+                    status,
+                });
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// The built-in checksum manifest, extended by `DataConfig::checksum_manifest`.
+    fn checksum_manifest(&self) -> Result<ChecksumManifest> {
+        let mut manifest = ChecksumManifest::builtin();
+        if let Some(path) = &self.config.checksum_manifest {
+            manifest.extend(ChecksumManifest::from_file(path)?);
+        }
+        Ok(manifest)
+    }
+
+    /// Checksums recorded when a component was installed, if any.
+    fn recorded_checksums(&self, component: DataComponent) -> Result<Vec<(String, String)>> {
+        let path = get_checksums_file_path(component, &self.active_data_dir());
+        match std::fs::read_to_string(&path) {
+            Ok(text) => Ok(integrity::parse_sha256sums(&text)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(Self::data_error(format!(
+                "Failed to read {}: {e}",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Record the checksum of every extracted file of a component in `dir`.
+    #[cfg(feature = "runtime-data")]
+    fn record_checksums(&self, component: DataComponent, dir: &Path) -> Result<()> {
+        fn visit_dir(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+            for entry in std::fs::read_dir(dir)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    visit_dir(&path, files)?;
+                } else {
+                    files.push(path);
+                }
+            }
+            Ok(())
+        }
+
+        let mut files = Vec::new();
+        for subdir in get_component_info(component).subdirs {
+            let subdir = dir.join(subdir);
+            if subdir.exists() {
+                visit_dir(&subdir, &mut files).map_err(|e| {
+                    Self::data_error(format!("Failed to list {}: {e}", subdir.display()))
+                })?;
+            }
+        }
+        files.sort();
+
+        let mut contents = String::new();
+        for file in files {
+            let relative = file.strip_prefix(dir).unwrap_or(&file);
+            contents.push_str(&format!(
+                "{}  {}\n",
+                sha256_file(&file)?,
+                relative.to_string_lossy()
+            ));
+        }
+
+        let path = get_checksums_file_path(component, dir);
+        std::fs::write(&path, contents)
+            .map_err(|e| Self::data_error(format!("Failed to write {}: {e}", path.display())))
+    }
+
+    /// Check a downloaded archive against the checksum manifest.
+    ///
+    /// Archives without a manifest entry are accepted, with a warning.
     #[cfg(feature = "runtime-data")]
+    fn verify_archive(
+        &self,
+        component: DataComponent,
+        info: &ComponentInfo,
+        path: &Path,
+    ) -> Result<()> {
+        let manifest = self.checksum_manifest()?;
+        let Some(expected) = manifest.get(component, &info.version, &info.filename) else {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(%component, version = %info.version, "no checksum for archive, installing it unverified");
+            return Ok(());
+        };
+
+        match integrity::check_file(path, Some(expected))? {
+            FileStatus::Verified => Ok(()),
+            status => Err(Self::data_error(format!(
+                "Downloaded {} {} failed verification: {status}",
+                info.name, info.version
+            ))),
+        }
+    }
+
+    /// Check the version of a specific component.
     fn check_component_version(&self, component: DataComponent) -> Result<Option<String>> {
-        let version_file = get_version_file_path(component, &self.data_dir);
+        let version_file = get_version_file_path(component, &self.active_data_dir());
         if !version_file.exists() {
             return Ok(None);
         }
@@ -305,192 +823,376 @@ impl DataManager {
         Ok(Some(version))
     }
 
-    /// Write version file for a component.
+    /// Write version file for a component in `dir`.
     #[cfg(feature = "runtime-data")]
-    fn write_version_file(&self, component: DataComponent, version: &str) -> Result<()> {
-        let version_file = get_version_file_path(component, &self.data_dir);
+    fn write_version_file(
+        &self,
+        component: DataComponent,
+        version: &str,
+        dir: &Path,
+    ) -> Result<()> {
+        let version_file = get_version_file_path(component, dir);
         std::fs::write(&version_file, version)
             .map_err(|e| Self::data_error(format!("Failed to write version file: {e}")))?;
         Ok(())
     }
 
-    /// Get GitHub release asset URL for a component.
+    /// Get the download URL of a component's archive.
+    ///
+    /// An entry in `DataConfig::component_urls` is used as is. Otherwise the
+    /// URL follows the GitHub release layout, `{base_url}/{release}/{filename}`,
+    /// with the model set's own location while `base_url` is left at its default.
     #[cfg(feature = "runtime-data")]
-    fn get_release_asset_url(&self, component: DataComponent) -> String {
-        let info = get_component_info(component);
-        format!("{}/{}/{}", LIBPOSTAL_BASE_URL, info.version, info.filename)
+    fn get_release_asset_url(&self, component: DataComponent, info: &ComponentInfo) -> String {
+        if let Some(url) = self.config.component_urls.get(&component) {
+            return url.clone();
+        }
+        let base_url = if self.config.base_url == LIBPOSTAL_BASE_URL {
+            self.config.parser_model.release_base_url()
+        } else {
+            &self.config.base_url
+        };
+        format!(
+            "{}/{}/{}",
+            base_url.trim_end_matches('/'),
+            info.release,
+            info.filename
+        )
     }
 
-    /// Download a file in multiple chunks using HTTP Range requests.
+    /// Find where a component's archive comes from.
+    ///
+    /// An archive in `DataConfig::archive_dir` wins over any URL, and
+    /// `file://` URLs are read in place.
     #[cfg(feature = "runtime-data")]
-    async fn download_release_multipart(&self, url: &str, filename: &Path, num_chunks: usize) -> Result<()> {
-        println!("Downloading multipart: {}, num_chunks={}", url, num_chunks);
-        
-        let chunk_size = self.config.chunk_size;
-        let mut download_tasks = Vec::new();
-
-        // Create tasks for each chunk
-        for i in 0..num_chunks {
-            let chunk_url = url.to_string();
-            let chunk_filename = filename.with_extension(format!("part{}", i + 1));
-            let offset = i * chunk_size;
-            let max_range = offset + chunk_size - 1;
-            
-            let client = reqwest::Client::builder()
-                .timeout(Duration::from_secs(self.config.chunk_timeout_seconds))
-                .build()
-                .map_err(|e| Self::data_error(format!("Failed to create HTTP client: {e}")))?;
-
-            let task = tokio::spawn(async move {
-                println!("Downloading part {}: filename={}, offset={}, max={}", 
-                    i + 1, chunk_filename.display(), offset, max_range);
-
-                let mut retries = 0;
-                const MAX_RETRIES: usize = 3;
-
-                while retries < MAX_RETRIES {
-                    let result = client
-                        .get(&chunk_url)
-                        .header("Range", format!("bytes={}-{}", offset, max_range))
-                        .send()
-                        .await;
-
-                    match result {
-                        Ok(response) => {
-                            if response.status().is_success() || response.status() == 206 {
-                                let bytes = response.bytes().await
-                                    .map_err(|e| format!("Failed to read response: {e}"))?;
-                                
-                                std::fs::write(&chunk_filename, &bytes)
-                                    .map_err(|e| format!("Failed to write chunk: {e}"))?;
-                                
-                                return Ok::<(), String>(());
-                            } else {
-                                return Err(format!("HTTP error: {}", response.status()));
-                            }
-                        }
-                        Err(e) => {
-                            retries += 1;
-                            if retries >= MAX_RETRIES {
-                                return Err(format!("Failed after {} retries: {e}", MAX_RETRIES));
-                            }
-                            tokio::time::sleep(Duration::from_secs(2_u64.pow(retries as u32))).await;
-                        }
-                    }
-                }
-                Err("Max retries exceeded".to_string())
-            });
+    fn archive_source(
+        &self,
+        component: DataComponent,
+        info: &ComponentInfo,
+    ) -> Result<ArchiveSource> {
+        if let Some(dir) = &self.config.archive_dir {
+            let path = dir.join(&info.filename);
+            if path.is_file() {
+                return Ok(ArchiveSource::Local(path));
+            }
+        }
 
-            download_tasks.push(task);
+        let url = self.get_release_asset_url(component, info);
+        if !url.starts_with("file:") {
+            return Ok(ArchiveSource::Remote(url));
         }
 
-        // Wait for all downloads to complete
-        let results = future::join_all(download_tasks).await;
-        
-        // Check for errors
-        for (i, result) in results.into_iter().enumerate() {
-            match result {
-                Ok(Ok(())) => {}
-                Ok(Err(e)) => return Err(Self::data_error(format!("Chunk {} failed: {e}", i + 1))),
-                Err(e) => return Err(Self::data_error(format!("Task {} panicked: {e}", i + 1))),
-            }
+        let path = reqwest::Url::parse(&url)
+            .ok()
+            .and_then(|url| url.to_file_path().ok())
+            .ok_or_else(|| Self::data_error(format!("Invalid file URL: {url}")))?;
+        if !path.is_file() {
+            return Err(Self::data_error(format!(
+                "Archive for libpostal {} not found at {}",
+                info.name,
+                path.display()
+            )));
         }
+        Ok(ArchiveSource::Local(path))
+    }
+
+    /// Build the HTTP client used for data downloads.
+    #[cfg(feature = "runtime-data")]
+    fn http_client(&self) -> Result<reqwest::Client> {
+        reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(self.config.timeout_seconds))
+            .read_timeout(Duration::from_secs(self.config.chunk_timeout_seconds))
+            .build()
+            .map_err(|e| Self::data_error(format!("Failed to create HTTP client: {e}")))
+    }
+
+    /// Download a file in multiple chunks using HTTP Range requests.
+    ///
+    /// Up to `download_workers` chunks are fetched at once, each streamed to
+    /// its own part file next to `filename`. Parts left by an interrupted
+    /// download are resumed rather than fetched again; they are removed once
+    /// the file has been reassembled.
+    #[cfg(feature = "runtime-data")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, filename, progress), fields(filename = %filename.display())))]
+    async fn download_release_multipart(
+        &self,
+        url: &str,
+        filename: &Path,
+        num_chunks: usize,
+        progress: &ProgressTracker,
+    ) -> Result<()> {
+        use futures::{StreamExt, TryStreamExt};
+
+        let client = self.http_client()?;
+        let chunk_size = self.config.chunk_size as u64;
+
+        futures::stream::iter(0..num_chunks)
+            .map(|i| {
+                let client = &client;
+                let offset = i as u64 * chunk_size;
+                let max_range = offset + chunk_size - 1;
+                async move {
+                    let part = part_path(filename, i);
+                    self.download_range(
+                        client,
+                        url,
+                        &part,
+                        offset,
+                        Some(max_range),
+                        progress,
+                        i + 1,
+                    )
+                    .await
+                }
+            })
+            .buffer_unordered(self.config.download_workers.max(1))
+            .try_collect::<Vec<()>>()
+            .await?;
 
         // Reassemble the file
         let mut output_file = std::fs::File::create(filename)
             .map_err(|e| Self::data_error(format!("Failed to create output file: {e}")))?;
 
         for i in 0..num_chunks {
-            let chunk_filename = filename.with_extension(format!("part{}", i + 1));
-            let chunk_data = std::fs::read(&chunk_filename)
+            let mut chunk_file = std::fs::File::open(part_path(filename, i))
                 .map_err(|e| Self::data_error(format!("Failed to read chunk {}: {e}", i + 1)))?;
-            
-            output_file.write_all(&chunk_data)
+            std::io::copy(&mut chunk_file, &mut output_file)
                 .map_err(|e| Self::data_error(format!("Failed to write to output file: {e}")))?;
-            
-            // Clean up chunk file
-            std::fs::remove_file(&chunk_filename).ok();
+        }
+        output_file
+            .flush()
+            .map_err(|e| Self::data_error(format!("Failed to write to output file: {e}")))?;
+
+        // Only drop the parts once the whole file is on disk
+        for i in 0..num_chunks {
+            let part = part_path(filename, i);
+            std::fs::remove_file(&part).ok();
+            std::fs::remove_file(validator_path(&part)).ok();
         }
 
-        println!("Multipart download completed: {}", filename.display());
         Ok(())
     }
 
+    /// Download bytes `offset..=max_range` of `url` into `path`.
+    ///
+    /// Whatever `path` already holds is kept and the download continues after
+    /// it; `max_range` of `None` reads to the end of the file. Failed attempts
+    /// are retried up to `max_retries` times with jittered exponential backoff.
+    /// Received bytes are counted against `chunk` in `progress`.
+    #[cfg(feature = "runtime-data")]
+    #[allow(clippy::too_many_arguments)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip(self, client, url, path, progress))
+    )]
+    async fn download_range(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+        path: &Path,
+        offset: u64,
+        max_range: Option<u64>,
+        progress: &ProgressTracker,
+        chunk: usize,
+    ) -> Result<()> {
+        if let Ok(metadata) = std::fs::metadata(path) {
+            progress.resumed(metadata.len());
+        }
+
+        let mut retries = 0;
+        loop {
+            match fetch_range(client, url, path, offset, max_range, progress, chunk).await {
+                Ok(()) => return Ok(()),
+                Err(failure) if failure.retryable && retries < self.config.max_retries => {
+                    retries += 1;
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(attempt = retries, error = %failure.message, "retrying chunk");
+                    tokio::time::sleep(retry_delay(retries)).await;
+                }
+                Err(failure) => {
+                    return Err(Self::data_error(format!(
+                        "Failed to download {} after {} attempt(s): {}",
+                        path.display(),
+                        retries + 1,
+                        failure.message
+                    )));
+                }
+            }
+        }
+    }
+
     /// Download a single component.
     #[cfg(feature = "runtime-data")]
     async fn download_component(&self, component: DataComponent) -> Result<()> {
-        let info = get_component_info(component);
-        let url = self.get_release_asset_url(component);
-        let local_path = self.data_dir.join(&info.filename);
+        self.download_version(component, &self.component_info(component))
+            .await
+    }
+
+    /// Download and install the version of a component described by `info`.
+    #[cfg(feature = "runtime-data")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(%component, version = %info.version)))]
+    async fn download_version(&self, component: DataComponent, info: &ComponentInfo) -> Result<()> {
+        let progress =
+            ProgressTracker::new(self.config.progress.clone(), component, info.num_chunks);
 
         // Check if update is needed
         let current_version = self.check_component_version(component)?;
         if let Some(current) = current_version {
             if current == info.version {
-                println!("libpostal {} up to date", info.name);
+                progress.report(DownloadStage::Complete);
                 return Ok(());
             }
         }
 
-        println!("New libpostal {} available", info.name);
-
-        // Download the file
+        let url = match self.archive_source(component, info)? {
+            ArchiveSource::Local(path) => {
+                return self.install_archive(component, info, &path, &progress);
+            }
+            ArchiveSource::Remote(url) => url,
+        };
+
+        // Download the file under a name that carries the version, so parts
+        // left by an interrupted download of another version are never resumed
+        let local_path = self.data_dir.join(download_file_name(info));
+        progress.report(DownloadStage::Downloading);
+        #[cfg(feature = "tracing")]
+        let started = Instant::now();
         if info.num_chunks > 1 {
-            self.download_release_multipart(&url, &local_path, info.num_chunks).await?;
+            self.download_release_multipart(&url, &local_path, info.num_chunks, &progress)
+                .await?;
         } else {
-            // Single file download
-            let client = reqwest::Client::builder()
-                .timeout(Duration::from_secs(self.config.timeout_seconds))
-                .build()
-                .map_err(|e| Self::data_error(format!("Failed to create HTTP client: {e}")))?;
+            let client = self.http_client()?;
+            self.download_range(&client, &url, &local_path, 0, None, &progress, 1)
+                .await?;
+        }
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            %url,
+            bytes = std::fs::metadata(&local_path).map_or(0, |metadata| metadata.len()),
+            duration_ms = started.elapsed().as_millis() as u64,
+            "downloaded archive"
+        );
 
-            let response = client.get(&url).send().await
-                .map_err(|e| Self::data_error(format!("Failed to download {}: {e}", url)))?;
+        let result = self.install_archive(component, info, &local_path, &progress);
 
-            if !response.status().is_success() {
-                return Err(Self::data_error(format!("Download failed with status: {}", response.status())));
-            }
+        // Clean up downloaded file; a corrupted one must not be resumed
+        std::fs::remove_file(&local_path).ok();
+        std::fs::remove_file(validator_path(&local_path)).ok();
+        result
+    }
 
-            let bytes = response.bytes().await
-                .map_err(|e| Self::data_error(format!("Failed to read response: {e}")))?;
+    /// Verify a component's archive and install it as a new data generation.
+    ///
+    /// The archive is unpacked into a staging directory, next to links to the
+    /// other installed components. Nothing changes for readers of the data
+    /// until the complete staging directory becomes the current generation.
+    /// The caller holds the data directory lock.
+    #[cfg(feature = "runtime-data")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(%component, version = %info.version, archive = %archive.display())))]
+    fn install_archive(
+        &self,
+        component: DataComponent,
+        info: &ComponentInfo,
+        archive: &Path,
+        progress: &ProgressTracker,
+    ) -> Result<()> {
+        // Refuse corrupted archives before doing any work
+        progress.report(DownloadStage::Verifying);
+        self.verify_archive(component, info, archive)?;
+
+        self.recover_locked()?;
+        let staging = generation::create_staging(&self.data_dir)
+            .map_err(|e| Self::data_error(format!("Failed to create staging directory: {e}")))?;
+
+        progress.report(DownloadStage::Extracting);
+        let installed = self
+            .stage_component(component, info, archive, &staging)
+            .and_then(|()| {
+                generation::publish(&self.data_dir, &staging).map_err(|e| {
+                    Self::data_error(format!("Failed to install libpostal {}: {e}", info.name))
+                })
+            });
+        if let Err(e) = installed {
+            std::fs::remove_dir_all(&staging).ok();
+            return Err(e);
+        }
 
-            std::fs::write(&local_path, &bytes)
-                .map_err(|e| Self::data_error(format!("Failed to write file: {e}")))?;
+        progress.report(DownloadStage::Complete);
+        Ok(())
+    }
+
+    /// Fill `staging` with a complete data directory that takes `component` from `archive`.
+    #[cfg(feature = "runtime-data")]
+    fn stage_component(
+        &self,
+        component: DataComponent,
+        info: &ComponentInfo,
+        archive: &Path,
+        staging: &Path,
+    ) -> Result<()> {
+        self.extract_tar_gz(archive, component, staging)?;
+
+        if let Some(missing) = required_files(component).find(|file| !staging.join(file).exists()) {
+            return Err(Self::data_error(format!(
+                "Archive {} is not a libpostal {}: it has no {missing}",
+                archive.display(),
+                info.name
+            )));
         }
 
-        // Remove old subdirectories
-        for subdir in &info.subdirs {
-            let path = self.data_dir.join(subdir);
-            if path.exists() {
-                std::fs::remove_dir_all(&path)
-                    .map_err(|e| Self::data_error(format!("Failed to remove {}: {e}", path.display())))?;
+        // Carry the other installed components over unchanged
+        let active = self.active_data_dir();
+        for &other in INSTALLED_COMPONENTS
+            .iter()
+            .filter(|&&other| other != component)
+        {
+            let subdirs = get_component_info(other).subdirs;
+            let files = [version_file_name(other), checksums_file_name(other)];
+            for name in subdirs.iter().map(String::as_str).chain(files) {
+                let source = active.join(name);
+                if source.exists() {
+                    generation::link_tree(&source, &staging.join(name)).map_err(|e| {
+                        Self::data_error(format!("Failed to link {}: {e}", source.display()))
+                    })?;
+                }
             }
         }
 
-        // Extract the archive
-        self.extract_tar_gz(&local_path)?;
-
-        // Clean up downloaded file
-        std::fs::remove_file(&local_path).ok();
-
-        // Write version file
-        self.write_version_file(component, &info.version)?;
-
-        println!("libpostal {} updated successfully", info.name);
-        Ok(())
+        // Record checksums for later verification, then the version
+        self.record_checksums(component, staging)?;
+        self.write_version_file(component, &info.version, staging)
     }
 
     /// Ensure data is available, downloading if necessary.
+    ///
+    /// Data of another model set than `DataConfig::parser_model` is replaced,
+    /// as are components not at their version in `DataConfig::pinned_versions`.
+    ///
+    /// Progress is reported to the callback set with
+    /// [`with_progress`](Self::with_progress) or `DataConfig::progress`.
+    /// Only one process or thread downloads into a data directory at a time;
+    /// the others wait for it, up to `DataConfig::lock_timeout_seconds`, and
+    /// then use the data it installed. Data that is already installed is used
+    /// without taking the lock, so the data directory may be read-only.
     #[cfg(feature = "runtime-data")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(data_dir = %self.data_dir.display())))]
     pub async fn ensure_data(&self) -> Result<()> {
-        if !self.is_data_available() {
-            if self.config.auto_download {
-                println!("Downloading libpostal data files...");
-                self.download_real_data().await?;
-            } else {
-                return Err(crate::error::Error::data_error(
-                    "Data files not available and auto_download is disabled",
-                ));
+        // Installed data is only read, so a read-only data directory works
+        // as long as nothing has to be installed.
+        match self.install_reason()? {
+            None => self.try_recover()?,
+            Some(reason) if !self.config.auto_download => {
+                return Err(Self::data_error(format!(
+                    "{reason} and auto_download is disabled"
+                )));
+            }
+            Some(_) => {
+                let _lock = self.lock_async().await?;
+                self.recover_locked()?;
+                // Another process may have installed it while we waited
+                if self.install_reason()?.is_some() {
+                    self.download_real_data().await?;
+                }
             }
         }
 
@@ -501,14 +1203,143 @@ impl DataManager {
         Ok(())
     }
 
+    /// Why the installed data has to be installed again, if it has to.
+    #[cfg(feature = "runtime-data")]
+    fn install_reason(&self) -> Result<Option<String>> {
+        let model = self.config.parser_model;
+        for (component, version) in &self.config.pinned_versions {
+            if ParserModel::from_version(version) != model {
+                return Err(Self::data_error(format!(
+                    "Pinned {component} version {version} is not a version of the {model} model set"
+                )));
+            }
+        }
+
+        if !self.is_data_available() {
+            return Ok(Some("Data files not available".to_string()));
+        }
+        let installed_model = self.installed_parser_model();
+        if installed_model != model {
+            return Ok(Some(format!(
+                "Installed data uses the {installed_model} parser model, not {model},"
+            )));
+        }
+        for (&component, version) in &self.config.pinned_versions {
+            let installed = self.check_component_version(component)?;
+            if installed.as_deref() != Some(version.as_str()) {
+                return Ok(Some(format!(
+                    "Installed {component} data is {}, not the pinned {version},",
+                    installed.as_deref().unwrap_or("unversioned")
+                )));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Versions to roll out: the built-in ones, extended by `DataConfig::release_manifest`.
+    ///
+    /// The manifest may be a local path, a `file://` URL or an HTTP(S) URL.
+    #[cfg(feature = "runtime-data")]
+    async fn release_manifest(&self) -> Result<ReleaseManifest> {
+        let mut manifest = ReleaseManifest::builtin();
+        let Some(location) = &self.config.release_manifest else {
+            return Ok(manifest);
+        };
+
+        let extra = if location.starts_with("http://") || location.starts_with("https://") {
+            let text = self
+                .http_client()?
+                .get(location)
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+                .map_err(|e| {
+                    crate::error::Error::network_error(format!(
+                        "Failed to fetch release manifest {location}: {e}"
+                    ))
+                })?
+                .text()
+                .await
+                .map_err(|e| {
+                    crate::error::Error::network_error(format!(
+                        "Failed to read release manifest {location}: {e}"
+                    ))
+                })?;
+            ReleaseManifest::parse(&text)?
+        } else if location.starts_with("file:") {
+            let path = reqwest::Url::parse(location)
+                .ok()
+                .and_then(|url| url.to_file_path().ok())
+                .ok_or_else(|| Self::data_error(format!("Invalid file URL: {location}")))?;
+            ReleaseManifest::from_file(path)?
+        } else {
+            ReleaseManifest::from_file(location)?
+        };
+        manifest.extend(extra);
+        Ok(manifest)
+    }
+
+    /// Compare each installed component with the version to roll out.
+    ///
+    /// The version to roll out is the pinned one from
+    /// `DataConfig::pinned_versions`, or else the newest in the release
+    /// manifest for the configured model set.
+    #[cfg(feature = "runtime-data")]
+    pub async fn check_for_updates(&self) -> Result<Vec<ComponentUpdate>> {
+        let manifest = self.release_manifest().await?;
+        let mut updates = Vec::new();
+        for &component in INSTALLED_COMPONENTS {
+            let pinned = self.config.pinned_versions.get(&component);
+            let target = match pinned {
+                Some(version) => version.clone(),
+                None => manifest
+                    .version(component, self.config.parser_model)
+                    .map_or_else(|| self.component_info(component).version, str::to_string),
+            };
+            updates.push(ComponentUpdate {
+                component,
+                installed: self.check_component_version(component)?,
+                target,
+                pinned: pinned.is_some(),
+            });
+        }
+        Ok(updates)
+    }
+
+    /// Install the version to roll out of `component`, or of every component
+    /// for `DataComponent::All`, where it differs from the installed one.
+    ///
+    /// See [`check_for_updates`](Self::check_for_updates) for which version
+    /// that is; it may be older than the installed one. Returns the updates
+    /// that were installed. Running processes keep their data until they
+    /// reload.
+    #[cfg(feature = "runtime-data")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(data_dir = %self.data_dir.display())))]
+    pub async fn upgrade(&self, component: DataComponent) -> Result<Vec<ComponentUpdate>> {
+        let _lock = self.lock_async().await?;
+        self.recover_locked()?;
+
+        let mut installed = Vec::new();
+        for update in self.check_for_updates().await? {
+            if (component == DataComponent::All || update.component == component)
+                && update.is_available()
+            {
+                let info = self
+                    .component_info(update.component)
+                    .at_version(&update.target);
+                self.download_version(update.component, &info).await?;
+                installed.push(update);
+            }
+        }
+        Ok(installed)
+    }
+
     /// Download real libpostal data files using native Rust implementation
     #[cfg(feature = "runtime-data")]
     async fn download_real_data(&self) -> Result<()> {
         // Create data directory
         Self::create_dir_all(&self.data_dir)?;
 
-        println!("Setting up libpostal data files...");
-
         // Try native component-based download first
         self.download_native_components().await
     }
@@ -518,33 +1349,158 @@ impl DataManager {
     async fn download_native_components(&self) -> Result<()> {
         // Download base data files
         self.download_component(DataComponent::Base).await?;
-        
+
         // Download parser model
         self.download_component(DataComponent::Parser).await?;
-        
+
         // Download language classifier model
-        self.download_component(DataComponent::LanguageClassifier).await?;
+        self.download_component(DataComponent::LanguageClassifier)
+            .await?;
 
         Ok(())
     }
 
-    /// Extract tar.gz file to data directory
+    /// Import a component from a local archive, without network access.
+    ///
+    /// `path` is a release archive such as `parser.tar.gz`. It is checked like
+    /// a downloaded one: against the checksum manifest, and for the files the
+    /// component needs. The component's version file is written afterwards,
+    /// so a later [`ensure_data`](Self::ensure_data) considers it up to date.
+    #[cfg(feature = "runtime-data")]
+    pub fn import_archive<P: AsRef<Path>>(&self, path: P, component: DataComponent) -> Result<()> {
+        if component == DataComponent::All {
+            return Err(Self::data_error(
+                "Each component has its own archive; use import_directory to import all of them",
+            ));
+        }
+        let path = path.as_ref();
+        if !path.is_file() {
+            return Err(Self::data_error(format!(
+                "Archive {} not found",
+                path.display()
+            )));
+        }
+
+        let _lock = self.lock()?;
+        self.import_archive_locked(path, component)
+    }
+
+    /// [`import_archive`](Self::import_archive) for a caller that holds the lock.
+    #[cfg(feature = "runtime-data")]
+    fn import_archive_locked(&self, path: &Path, component: DataComponent) -> Result<()> {
+        let progress = ProgressTracker::new(self.config.progress.clone(), component, 0);
+        self.install_archive(component, &self.component_info(component), path, &progress)
+    }
+
+    /// Import every component whose release archive is in `dir`.
+    ///
+    /// Archives keep their release names: `libpostal_data.tar.gz`,
+    /// `parser.tar.gz` and `language_classifier.tar.gz`. Returns the imported
+    /// components, and an error if `dir` holds none of the archives.
+    #[cfg(feature = "runtime-data")]
+    pub fn import_directory<P: AsRef<Path>>(&self, dir: P) -> Result<Vec<DataComponent>> {
+        let dir = dir.as_ref();
+        let mut imported = Vec::new();
+        let _lock = self.lock()?;
+        for &component in INSTALLED_COMPONENTS {
+            let archive = dir.join(get_component_info(component).filename);
+            if archive.is_file() {
+                self.import_archive_locked(&archive, component)?;
+                imported.push(component);
+            }
+        }
+
+        if imported.is_empty() {
+            return Err(Self::data_error(format!(
+                "No libpostal data archives found in {}",
+                dir.display()
+            )));
+        }
+        Ok(imported)
+    }
+
+    /// Extract the entries of a component's archive into `target`.
+    ///
+    /// Entries with absolute paths or `..` components and links are refused,
+    /// so an archive cannot write outside `target`. Entries outside the
+    /// component's subdirectories are skipped.
     #[cfg(feature = "runtime-data")]
-    fn extract_tar_gz(&self, archive_path: &std::path::Path) -> Result<()> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(%component)))]
+    fn extract_tar_gz(
+        &self,
+        archive_path: &Path,
+        component: DataComponent,
+        target: &Path,
+    ) -> Result<()> {
         use flate2::read::GzDecoder;
         use std::fs::File;
+        use std::path::Component;
         use tar::Archive;
 
+        let subdirs = get_component_info(component).subdirs;
         let file = File::open(archive_path)
-            .map_err(|e| crate::error::Error::data_error(format!("Failed to open archive: {e}")))?;
+            .map_err(|e| Self::data_error(format!("Failed to open archive: {e}")))?;
 
+        #[cfg(feature = "tracing")]
+        let (started, mut files, mut bytes) = (Instant::now(), 0u64, 0u64);
         let gz = GzDecoder::new(file);
         let mut archive = Archive::new(gz);
+        let entries = archive
+            .entries()
+            .map_err(|e| Self::data_error(format!("Failed to extract archive: {e}")))?;
+
+        for entry in entries {
+            let mut entry =
+                entry.map_err(|e| Self::data_error(format!("Failed to extract archive: {e}")))?;
+            let path = entry
+                .path()
+                .map_err(|e| Self::data_error(format!("Failed to extract archive: {e}")))?
+                .into_owned();
+
+            if path
+                .components()
+                .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+            {
+                return Err(Self::data_error(format!(
+                    "Archive entry {} points outside the data directory",
+                    path.display()
+                )));
+            }
+            let kind = entry.header().entry_type();
+            if kind.is_symlink() || kind.is_hard_link() {
+                return Err(Self::data_error(format!(
+                    "Archive entry {} is a link",
+                    path.display()
+                )));
+            }
+            if !kind.is_file() && !kind.is_dir() {
+                continue;
+            }
+            let top = path.components().find(|c| *c != Component::CurDir);
+            if !subdirs
+                .iter()
+                .any(|dir| top == Some(Component::Normal(dir.as_ref())))
+            {
+                continue;
+            }
 
-        archive.unpack(&self.data_dir).map_err(|e| {
-            crate::error::Error::data_error(format!("Failed to extract archive: {e}"))
-        })?;
+            entry.unpack_in(target).map_err(|e| {
+                Self::data_error(format!("Failed to extract {}: {e}", path.display()))
+            })?;
+            #[cfg(feature = "tracing")]
+            {
+                files += 1;
+                bytes += entry.size();
+            }
+        }
 
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            files,
+            bytes,
+            duration_ms = started.elapsed().as_millis() as u64,
+            "extracted archive"
+        );
         Ok(())
     }
 }
@@ -555,6 +1511,319 @@ impl Default for DataManager {
     }
 }
 
+/// Name a component's archive is downloaded to, e.g. "senzing-v1.1.0-parser.tar.gz".
+#[cfg(feature = "runtime-data")]
+fn download_file_name(info: &ComponentInfo) -> String {
+    format!("{}-{}", info.version, info.filename)
+}
+
+/// Path of the part file holding chunk `index` of `filename`.
+#[cfg(feature = "runtime-data")]
+fn part_path(filename: &Path, index: usize) -> PathBuf {
+    filename.with_extension(format!("part{}", index + 1))
+}
+
+/// Path recording the validator of the response a partial download began with.
+#[cfg(feature = "runtime-data")]
+fn validator_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".validator");
+    PathBuf::from(name)
+}
+
+/// The validator to send as `If-Range` when resuming from `response`: a strong
+/// ETag, or else the Last-Modified date.
+#[cfg(feature = "runtime-data")]
+fn response_validator(response: &reqwest::Response) -> Option<String> {
+    let headers = response.headers();
+    let etag = headers
+        .get(reqwest::header::ETAG)
+        .and_then(|value| value.to_str().ok())
+        .filter(|etag| !etag.starts_with("W/"));
+    etag.or_else(|| headers.get(reqwest::header::LAST_MODIFIED)?.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Backoff before retry number `retry`, with up to half of it taken off at random.
+#[cfg(feature = "runtime-data")]
+fn retry_delay(retry: usize) -> Duration {
+    use std::hash::BuildHasher;
+
+    let delay = RETRY_BASE_DELAY
+        .saturating_mul(1 << retry.saturating_sub(1).min(16))
+        .min(RETRY_MAX_DELAY);
+    let millis = delay.as_millis() as u64;
+    let jitter = std::collections::hash_map::RandomState::new().hash_one(retry) % (millis / 2 + 1);
+    Duration::from_millis(millis - jitter)
+}
+
+/// Where a component's archive is read from.
+#[cfg(feature = "runtime-data")]
+#[derive(Debug, PartialEq, Eq)]
+enum ArchiveSource {
+    /// An archive already on disk
+    Local(PathBuf),
+    /// An archive to download
+    Remote(String),
+}
+
+/// A failed download attempt.
+#[cfg(feature = "runtime-data")]
+struct FetchFailure {
+    message: String,
+    retryable: bool,
+}
+
+#[cfg(feature = "runtime-data")]
+impl FetchFailure {
+    fn transient(message: impl std::fmt::Display) -> Self {
+        Self {
+            message: message.to_string(),
+            retryable: true,
+        }
+    }
+
+    fn fatal(message: impl std::fmt::Display) -> Self {
+        Self {
+            message: message.to_string(),
+            retryable: false,
+        }
+    }
+}
+
+/// Counts the bytes of one component's download and reports them.
+#[cfg(feature = "runtime-data")]
+struct ProgressTracker {
+    callback: Option<ProgressCallback>,
+    component: DataComponent,
+    total_chunks: usize,
+    total_bytes: AtomicU64,
+    downloaded_bytes: AtomicU64,
+    /// Bytes received by this run, which the speed is based on
+    received_bytes: AtomicU64,
+    current_chunk: std::sync::atomic::AtomicUsize,
+    started: Instant,
+    last_report: std::sync::Mutex<Option<Instant>>,
+}
+
+#[cfg(feature = "runtime-data")]
+impl ProgressTracker {
+    fn new(
+        callback: Option<ProgressCallback>,
+        component: DataComponent,
+        total_chunks: usize,
+    ) -> Self {
+        Self {
+            callback,
+            component,
+            total_chunks,
+            total_bytes: AtomicU64::new(0),
+            downloaded_bytes: AtomicU64::new(0),
+            received_bytes: AtomicU64::new(0),
+            current_chunk: std::sync::atomic::AtomicUsize::new(0),
+            started: Instant::now(),
+            last_report: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Record the size of the whole file once the server reports it.
+    fn set_total(&self, total: u64) {
+        self.total_bytes.store(total, Ordering::Relaxed);
+    }
+
+    /// Count bytes already on disk from an interrupted download.
+    fn resumed(&self, bytes: u64) {
+        self.downloaded_bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Stop counting resumed bytes that turned out to be stale.
+    fn discarded(&self, bytes: u64) {
+        self.downloaded_bytes.fetch_sub(bytes, Ordering::Relaxed);
+    }
+
+    /// Count bytes received for `chunk`, reporting at most every `PROGRESS_INTERVAL`.
+    fn received(&self, chunk: usize, bytes: u64) {
+        self.received_bytes.fetch_add(bytes, Ordering::Relaxed);
+        self.downloaded_bytes.fetch_add(bytes, Ordering::Relaxed);
+        self.current_chunk.store(chunk, Ordering::Relaxed);
+
+        let now = Instant::now();
+        {
+            let mut last = self.last_report.lock().unwrap_or_else(|p| p.into_inner());
+            if last.is_some_and(|last| now - last < PROGRESS_INTERVAL) {
+                return;
+            }
+            *last = Some(now);
+        }
+        self.report(DownloadStage::Downloading);
+    }
+
+    fn report(&self, stage: DownloadStage) {
+        let Some(callback) = &self.callback else {
+            return;
+        };
+
+        let downloaded_bytes = self.downloaded_bytes.load(Ordering::Relaxed);
+        let mut total_bytes = self.total_bytes.load(Ordering::Relaxed);
+        if stage != DownloadStage::Downloading && total_bytes == 0 {
+            total_bytes = downloaded_bytes;
+        }
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let speed_bps = if elapsed > 0.0 {
+            (self.received_bytes.load(Ordering::Relaxed) as f64 / elapsed) as u64
+        } else {
+            0
+        };
+
+        callback.emit(&DownloadProgress {
+            component: self.component,
+            stage,
+            total_bytes,
+            downloaded_bytes,
+            current_chunk: self.current_chunk.load(Ordering::Relaxed),
+            total_chunks: self.total_chunks,
+            speed_bps,
+        });
+    }
+}
+
+/// Stream bytes `offset..=max_range` of `url` onto the end of `path`, starting after what it already holds.
+///
+/// A resumed download sends `If-Range` with the validator its first response
+/// carried. Should the file have changed on the server since, what `path`
+/// holds is discarded and the attempt fails so that a retry starts over.
+#[cfg(feature = "runtime-data")]
+async fn fetch_range(
+    client: &reqwest::Client,
+    url: &str,
+    path: &Path,
+    offset: u64,
+    max_range: Option<u64>,
+    progress: &ProgressTracker,
+    chunk: usize,
+) -> std::result::Result<(), FetchFailure> {
+    use futures::StreamExt;
+    use reqwest::StatusCode;
+    use tokio::io::AsyncWriteExt;
+
+    let existing = tokio::fs::metadata(path)
+        .await
+        .map(|m| m.len())
+        .unwrap_or(0);
+    let start = offset + existing;
+    if max_range.is_some_and(|max| start > max) {
+        return Ok(());
+    }
+
+    let validator_path = validator_path(path);
+    let validator = if existing > 0 {
+        tokio::fs::read_to_string(&validator_path).await.ok()
+    } else {
+        None
+    };
+
+    let ranged = start > 0 || max_range.is_some();
+    let mut request = client.get(url);
+    if ranged {
+        let max = max_range.map(|max| max.to_string()).unwrap_or_default();
+        request = request.header(reqwest::header::RANGE, format!("bytes={start}-{max}"));
+    }
+    if let Some(validator) = &validator {
+        request = request.header(reqwest::header::IF_RANGE, validator.as_str());
+    }
+
+    let response = request.send().await.map_err(FetchFailure::transient)?;
+    let status = response.status();
+    if status == StatusCode::RANGE_NOT_SATISFIABLE {
+        // Nothing left past `start`: the part already runs to the end of the file
+        return Ok(());
+    }
+    if !status.is_success() {
+        let failure = format!("HTTP error: {status}");
+        return Err(
+            if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+                FetchFailure::transient(failure)
+            } else {
+                FetchFailure::fatal(failure)
+            },
+        );
+    }
+    if validator.is_some() && status == StatusCode::OK {
+        // The validator no longer matches, so the bytes on disk are from another file
+        tokio::fs::remove_file(path).await.ok();
+        tokio::fs::remove_file(&validator_path).await.ok();
+        progress.discarded(existing);
+        return Err(FetchFailure::transient(format!(
+            "{url} changed on the server"
+        )));
+    }
+    if ranged && status != StatusCode::PARTIAL_CONTENT {
+        return Err(FetchFailure::fatal(
+            "server does not support range requests",
+        ));
+    }
+    if existing == 0 {
+        match response_validator(&response) {
+            Some(validator) => tokio::fs::write(&validator_path, validator).await,
+            None => tokio::fs::remove_file(&validator_path).await.or(Ok(())),
+        }
+        .map_err(|e| {
+            FetchFailure::fatal(format!("Failed to write {}: {e}", validator_path.display()))
+        })?;
+    }
+
+    // Content-Range ends in the size of the whole file; without a range the body is the file
+    let total = match response.headers().get(reqwest::header::CONTENT_RANGE) {
+        Some(range) => range
+            .to_str()
+            .ok()
+            .and_then(|range| range.rsplit_once('/'))
+            .and_then(|(_, total)| total.parse().ok()),
+        None => response.content_length(),
+    };
+    if let Some(total) = total {
+        progress.set_total(total);
+    }
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await
+        .map_err(|e| FetchFailure::fatal(format!("Failed to open {}: {e}", path.display())))?;
+
+    let mut body = response.bytes_stream();
+    while let Some(bytes) = body.next().await {
+        let bytes = bytes.map_err(FetchFailure::transient)?;
+        file.write_all(&bytes)
+            .await
+            .map_err(|e| FetchFailure::fatal(format!("Failed to write {}: {e}", path.display())))?;
+        progress.received(chunk, bytes.len() as u64);
+    }
+    file.flush()
+        .await
+        .map_err(|e| FetchFailure::fatal(format!("Failed to write {}: {e}", path.display())))?;
+
+    Ok(())
+}
+
+/// Get the data directory bundled with the `bundled-data` feature.
+///
+/// A `libpostal-data` directory next to the running executable comes first,
+/// so a binary can be deployed together with the data its build placed next
+/// to it. Otherwise this is where the build placed the data.
+#[cfg(feature = "bundled-data")]
+pub fn bundled_data_dir() -> Option<PathBuf> {
+    let beside_executable = std::env::current_exe()
+        .ok()
+        .and_then(|exe| Some(exe.parent()?.join(BUNDLED_DATA_DIR_NAME)));
+    let built = option_env!("LIBPOSTAL_BUNDLED_DATA_DIR").map(PathBuf::from);
+    beside_executable
+        .into_iter()
+        .chain(built)
+        .find(|dir| dir.is_dir())
+}
+
 /// Get the default data directory for libpostal.
 pub fn default_data_dir() -> PathBuf {
     // Check for environment variable first
@@ -565,6 +1834,12 @@ pub fn default_data_dir() -> PathBuf {
         }
     }
 
+    // Data bundled with the `bundled-data` feature, needing no download
+    #[cfg(feature = "bundled-data")]
+    if let Some(bundled) = bundled_data_dir() {
+        return bundled;
+    }
+
     // Check for data downloaded during build (compile-time env var)
     if let Some(built_data_dir) = option_env!("LIBPOSTAL_BUILT_DATA_DIR") {
         let path = PathBuf::from(built_data_dir);
@@ -597,8 +1872,11 @@ pub struct DataConfig {
     pub auto_download: bool,
     /// Whether to verify data integrity
     pub verify_integrity: bool,
-    /// Base URL for data downloads
+    /// Base URL for data downloads; archives are fetched from
+    /// `{base_url}/{release}/{filename}`, which may be a `file://` URL
     pub base_url: String,
+    /// Model set to install (default: the libpostal project's models)
+    pub parser_model: ParserModel,
     /// Connection timeout for downloads
     pub timeout_seconds: u64,
     /// Number of parallel download workers (default: 12)
@@ -607,8 +1885,26 @@ pub struct DataConfig {
     pub chunk_size: usize,
     /// Number of retry attempts for failed downloads (default: 3)
     pub max_retries: usize,
-    /// Timeout for individual chunk downloads (default: 30s)
+    /// Longest a download may stall without receiving data (default: 30s)
     pub chunk_timeout_seconds: u64,
+    /// Archive URLs that replace `base_url` for single components (default: none)
+    pub component_urls: HashMap<DataComponent, String>,
+    /// Directory of pre-fetched archives, used instead of downloading
+    /// whenever it holds a component's archive (default: none)
+    pub archive_dir: Option<PathBuf>,
+    /// Receives download progress (default: none)
+    pub progress: Option<ProgressCallback>,
+    /// Extra checksum manifest merged over the built-in one (default: none)
+    pub checksum_manifest: Option<PathBuf>,
+    /// Path or URL of a release manifest naming the versions to upgrade to
+    /// (default: none, the versions built into the crate)
+    pub release_manifest: Option<String>,
+    /// Versions to install instead of the latest, as recorded in version
+    /// files, e.g. `"v1.0.0"` or `"senzing-v1.1.0"` (default: none)
+    pub pinned_versions: HashMap<DataComponent, String>,
+    /// Longest to wait for another process installing into the same data
+    /// directory (default: 30 minutes)
+    pub lock_timeout_seconds: u64,
 }
 
 impl Default for DataConfig {
@@ -617,12 +1913,20 @@ impl Default for DataConfig {
             data_dir: default_data_dir(),
             auto_download: true,
             verify_integrity: true,
-            base_url: "https://github.com/openvenues/libpostal/releases/download/v1.1/".to_string(),
+            base_url: LIBPOSTAL_BASE_URL.to_string(),
+            parser_model: ParserModel::Default,
             timeout_seconds: 300, // 5 minutes
             download_workers: DEFAULT_NUM_WORKERS,
             chunk_size: CHUNK_SIZE,
             max_retries: 3,
             chunk_timeout_seconds: 30,
+            checksum_manifest: None,
+            release_manifest: None,
+            pinned_versions: HashMap::new(),
+            component_urls: HashMap::new(),
+            archive_dir: None,
+            progress: None,
+            lock_timeout_seconds: 30 * 60,
         }
     }
 }
@@ -648,5 +1952,762 @@ mod tests {
         assert!(config.auto_download);
         assert!(config.verify_integrity);
         assert!(config.timeout_seconds > 0);
+        assert!(config.checksum_manifest.is_none());
+    }
+
+    #[test]
+    fn test_verify_integrity_detects_corruption() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in REQUIRED_DATA_FILES {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, file.as_bytes()).unwrap();
+        }
+
+        let numex = "numex/numex.dat";
+        let manifest = dir.path().join("checksums.txt");
+        std::fs::write(
+            &manifest,
+            format!(
+                "base v1.0.0 {} {numex}\n",
+                sha256_file(dir.path().join(numex)).unwrap()
+            ),
+        )
+        .unwrap();
+
+        let manager = DataManager::with_config(DataConfig {
+            data_dir: dir.path().to_path_buf(),
+            checksum_manifest: Some(manifest),
+            ..DataConfig::default()
+        });
+
+        let report = manager.verify_integrity().unwrap();
+        assert!(report.is_ok());
+        assert_eq!(report.verified(), 1);
+        assert_eq!(report.unverified(), REQUIRED_DATA_FILES.len() - 1);
+
+        std::fs::write(dir.path().join(numex), "corrupted").unwrap();
+        std::fs::remove_file(
+            dir.path()
+                .join("language_classifier/language_classifier.dat"),
+        )
+        .unwrap();
+
+        let report = manager.verify_integrity().unwrap();
+        let corrupted: Vec<_> = report.corrupted().map(|f| f.path.clone()).collect();
+        assert_eq!(
+            corrupted,
+            vec![
+                PathBuf::from(numex),
+                PathBuf::from("language_classifier/language_classifier.dat"),
+            ]
+        );
+        assert!(report.into_result().is_err());
+    }
+
+    /// Local stand-in for the release server: serves `body` with Range support.
+    #[cfg(feature = "runtime-data")]
+    struct TestServer {
+        url: String,
+        body: std::sync::Arc<Vec<u8>>,
+        /// Requests still to be answered with 503
+        failures: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+        /// Body bytes sent so far
+        served: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+        /// Most requests in flight at once
+        max_active: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[cfg(feature = "runtime-data")]
+    impl TestServer {
+        async fn start(len: usize, failures: usize) -> Self {
+            use std::sync::Arc;
+            use std::sync::atomic::{AtomicUsize, Ordering};
+            use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let server = Self {
+                url: format!("http://{}/data.tar.gz", listener.local_addr().unwrap()),
+                body: Arc::new((0..len).map(|i| (i % 251) as u8).collect()),
+                failures: Arc::new(AtomicUsize::new(failures)),
+                served: Arc::new(AtomicUsize::new(0)),
+                max_active: Arc::new(AtomicUsize::new(0)),
+            };
+            let active = Arc::new(AtomicUsize::new(0));
+
+            let (body, failures, served, max_active) = (
+                server.body.clone(),
+                server.failures.clone(),
+                server.served.clone(),
+                server.max_active.clone(),
+            );
+            tokio::spawn(async move {
+                loop {
+                    let (mut socket, _) = listener.accept().await.unwrap();
+                    let (body, failures, served, max_active, active) = (
+                        body.clone(),
+                        failures.clone(),
+                        served.clone(),
+                        max_active.clone(),
+                        active.clone(),
+                    );
+                    tokio::spawn(async move {
+                        let mut request = Vec::new();
+                        let mut buf = [0; 1024];
+                        while !request.ends_with(b"\r\n\r\n") {
+                            let n = socket.read(&mut buf).await.unwrap();
+                            if n == 0 {
+                                return;
+                            }
+                            request.extend_from_slice(&buf[..n]);
+                        }
+
+                        let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                        max_active.fetch_max(now, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(20)).await;
+
+                        let request = String::from_utf8_lossy(&request).to_lowercase();
+                        let current = request
+                            .lines()
+                            .find_map(|line| line.strip_prefix("if-range: "))
+                            .is_none_or(|validator| validator == "\"v1\"");
+                        let range = request.lines().filter(|_| current).find_map(|line| {
+                            let (start, end) =
+                                line.strip_prefix("range: bytes=")?.split_once('-')?;
+                            let start: usize = start.parse().ok()?;
+                            let end = end
+                                .parse::<usize>()
+                                .map_or(body.len() - 1, |end| end.min(body.len() - 1));
+                            Some((start, end))
+                        });
+
+                        let failed = failures
+                            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                            .is_ok();
+                        let (head, content): (String, &[u8]) = match range {
+                            _ if failed => ("503 Service Unavailable".into(), &[]),
+                            Some((start, _)) if start >= body.len() => {
+                                ("416 Range Not Satisfiable".into(), &[])
+                            }
+                            Some((start, end)) => (
+                                format!(
+                                    "206 Partial Content\r\nContent-Range: bytes {start}-{end}/{}",
+                                    body.len()
+                                ),
+                                &body[start..=end],
+                            ),
+                            None => ("200 OK".into(), &body[..]),
+                        };
+                        served.fetch_add(content.len(), Ordering::SeqCst);
+
+                        let response = format!(
+                            "HTTP/1.1 {head}\r\nETag: \"v1\"\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                            content.len()
+                        );
+                        socket.write_all(response.as_bytes()).await.unwrap();
+                        socket.write_all(content).await.unwrap();
+                        active.fetch_sub(1, Ordering::SeqCst);
+                    });
+                }
+            });
+
+            server
+        }
+
+        fn served(&self) -> usize {
+            self.served.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    #[cfg(feature = "runtime-data")]
+    fn download_manager(dir: &Path, max_retries: usize) -> DataManager {
+        DataManager::with_config(DataConfig {
+            data_dir: dir.to_path_buf(),
+            chunk_size: 100,
+            download_workers: 3,
+            max_retries,
+            ..DataConfig::default()
+        })
+    }
+
+    #[cfg(feature = "runtime-data")]
+    #[tokio::test]
+    async fn test_multipart_download_bounded_by_workers() {
+        let server = TestServer::start(950, 0).await;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.tar.gz");
+
+        let tracker = ProgressTracker::new(None, DataComponent::Parser, 10);
+        download_manager(dir.path(), 0)
+            .download_release_multipart(&server.url, &path, 10, &tracker)
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), *server.body);
+        assert!(server.max_active.load(std::sync::atomic::Ordering::SeqCst) <= 3);
+        assert!(!part_path(&path, 0).exists());
+    }
+
+    #[cfg(feature = "runtime-data")]
+    #[tokio::test]
+    async fn test_multipart_download_resumes_parts() {
+        let server = TestServer::start(950, 0).await;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.tar.gz");
+
+        // Chunk 1 finished and chunk 2 interrupted after 40 bytes
+        std::fs::write(part_path(&path, 0), &server.body[..100]).unwrap();
+        std::fs::write(part_path(&path, 1), &server.body[100..140]).unwrap();
+
+        let (callback, progress) = ProgressCallback::watch();
+        let tracker = ProgressTracker::new(Some(callback), DataComponent::Parser, 10);
+        download_manager(dir.path(), 0)
+            .download_release_multipart(&server.url, &path, 10, &tracker)
+            .await
+            .unwrap();
+        tracker.report(DownloadStage::Complete);
+
+        assert_eq!(std::fs::read(&path).unwrap(), *server.body);
+        assert_eq!(server.served(), 950 - 140);
+
+        let progress = progress.borrow().clone().unwrap();
+        assert_eq!(progress.component, DataComponent::Parser);
+        assert_eq!(progress.stage, DownloadStage::Complete);
+        assert_eq!(progress.total_bytes, 950);
+        assert_eq!(progress.downloaded_bytes, 950);
+    }
+
+    #[cfg(feature = "runtime-data")]
+    #[tokio::test]
+    async fn test_download_discards_parts_of_changed_file() {
+        let server = TestServer::start(950, 0).await;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.tar.gz");
+
+        // Chunk 2 was interrupted while the server had another file
+        let part = part_path(&path, 1);
+        std::fs::write(&part, [0; 40]).unwrap();
+        std::fs::write(validator_path(&part), "\"v0\"").unwrap();
+
+        let tracker = ProgressTracker::new(None, DataComponent::Parser, 10);
+        download_manager(dir.path(), 1)
+            .download_release_multipart(&server.url, &path, 10, &tracker)
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), *server.body);
+        assert!(!validator_path(&part).exists());
+    }
+
+    #[cfg(feature = "runtime-data")]
+    #[test]
+    fn test_download_file_name_carries_version() {
+        let info = get_component_info(DataComponent::Parser);
+        let senzing = info.clone().at_version("senzing-v1.1.0");
+        assert_ne!(download_file_name(&info), download_file_name(&senzing));
+        assert_eq!(
+            download_file_name(&senzing),
+            format!("senzing-v1.1.0-{}", info.filename)
+        );
+    }
+
+    #[cfg(feature = "runtime-data")]
+    #[tokio::test]
+    async fn test_download_retries_transient_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = download_manager(dir.path(), 2);
+        let client = manager.http_client().unwrap();
+        let tracker = ProgressTracker::new(None, DataComponent::Base, 1);
+
+        let server = TestServer::start(300, 2).await;
+        let path = dir.path().join("recovered");
+        manager
+            .download_range(&client, &server.url, &path, 0, None, &tracker, 1)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), *server.body);
+
+        let server = TestServer::start(300, 3).await;
+        let path = dir.path().join("failed");
+        let error = manager
+            .download_range(&client, &server.url, &path, 0, None, &tracker, 1)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("after 3 attempt(s)"), "{error}");
+    }
+
+    /// Write a gzipped tarball holding `files` to `path`.
+    #[cfg(feature = "runtime-data")]
+    fn write_archive(path: &Path, files: &[(&str, &[u8])]) {
+        let encoder = flate2::write::GzEncoder::new(
+            std::fs::File::create(path).unwrap(),
+            flate2::Compression::fast(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+        for (name, contents) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, name, *contents).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    /// Archive entries for the required files of `component`.
+    #[cfg(feature = "runtime-data")]
+    fn component_files(component: DataComponent) -> Vec<(&'static str, &'static [u8])> {
+        required_files(component)
+            .map(|file| (file, file.as_bytes()))
+            .collect()
+    }
+
+    #[cfg(feature = "runtime-data")]
+    #[test]
+    fn test_release_asset_url() {
+        let mut config = DataConfig {
+            base_url: "https://mirror.example.com/libpostal/".to_string(),
+            ..DataConfig::default()
+        };
+        config.component_urls.insert(
+            DataComponent::Parser,
+            "https://cdn.example.com/parser.tar.gz".to_string(),
+        );
+        let manager = DataManager::with_config(config);
+        let url = |manager: &DataManager, component| {
+            manager.get_release_asset_url(component, &manager.component_info(component))
+        };
+
+        assert_eq!(
+            url(&manager, DataComponent::Base),
+            "https://mirror.example.com/libpostal/v1.0.0/libpostal_data.tar.gz"
+        );
+        assert_eq!(
+            url(&manager, DataComponent::Parser),
+            "https://cdn.example.com/parser.tar.gz"
+        );
+        assert_eq!(
+            url(&DataManager::new(), DataComponent::LanguageClassifier),
+            format!("{LIBPOSTAL_BASE_URL}/v1.0.0/language_classifier.tar.gz")
+        );
+
+        let senzing = DataManager::with_config(DataConfig {
+            parser_model: ParserModel::Senzing,
+            ..DataConfig::default()
+        });
+        assert_eq!(
+            url(&senzing, DataComponent::Parser),
+            format!("{SENZING_BASE_URL}/v1.1.0/parser.tar.gz")
+        );
+    }
+
+    #[cfg(feature = "runtime-data")]
+    #[tokio::test]
+    async fn test_switch_parser_model() {
+        let archives = tempfile::tempdir().unwrap();
+        for &component in INSTALLED_COMPONENTS {
+            write_archive(
+                &archives.path().join(get_component_info(component).filename),
+                &component_files(component),
+            );
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let manager = |parser_model, auto_download| {
+            DataManager::with_config(DataConfig {
+                data_dir: dir.path().to_path_buf(),
+                archive_dir: Some(archives.path().to_path_buf()),
+                parser_model,
+                auto_download,
+                ..DataConfig::default()
+            })
+        };
+
+        let default = manager(ParserModel::Default, true);
+        default.ensure_data().await.unwrap();
+        assert_eq!(default.installed_parser_model(), ParserModel::Default);
+
+        // Without downloads, data of the wrong model set is an error
+        let err = manager(ParserModel::Senzing, false)
+            .ensure_data()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("default parser model"), "{err}");
+
+        let senzing = manager(ParserModel::Senzing, true);
+        senzing.ensure_data().await.unwrap();
+        assert_eq!(senzing.installed_parser_model(), ParserModel::Senzing);
+        for &component in INSTALLED_COMPONENTS {
+            assert_eq!(
+                senzing
+                    .check_component_version(component)
+                    .unwrap()
+                    .as_deref(),
+                Some("senzing-v1.1.0")
+            );
+        }
+        assert_eq!(
+            installed_parser_model(&senzing.active_data_dir()),
+            ParserModel::Senzing
+        );
+    }
+
+    #[cfg(feature = "runtime-data")]
+    #[tokio::test]
+    async fn test_pinned_versions_and_upgrade() {
+        let archives = tempfile::tempdir().unwrap();
+        for &component in INSTALLED_COMPONENTS {
+            write_archive(
+                &archives.path().join(get_component_info(component).filename),
+                &component_files(component),
+            );
+        }
+        let releases = archives.path().join("releases.txt");
+        std::fs::write(&releases, "parser v1.2.0\n").unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = DataConfig {
+            data_dir: dir.path().to_path_buf(),
+            archive_dir: Some(archives.path().to_path_buf()),
+            release_manifest: Some(releases.display().to_string()),
+            ..DataConfig::default()
+        };
+
+        let mut pinned = config.clone();
+        pinned
+            .pinned_versions
+            .insert(DataComponent::Parser, "v0.9.0".to_string());
+        let pinned = DataManager::with_config(pinned);
+        pinned.ensure_data().await.unwrap();
+        let versions = pinned.installed_versions().unwrap();
+        assert_eq!(versions[&DataComponent::Parser], "v0.9.0");
+        assert_eq!(
+            versions[&DataComponent::Base],
+            LIBPOSTAL_DATA_FILE_LATEST_VERSION
+        );
+        assert!(pinned.upgrade(DataComponent::All).await.unwrap().is_empty());
+
+        let manager = DataManager::with_config(config.clone());
+        let updates = manager.check_for_updates().await.unwrap();
+        let available: Vec<_> = updates
+            .iter()
+            .filter(|update| update.is_available())
+            .collect();
+        assert_eq!(available.len(), 1);
+        assert_eq!(available[0].component, DataComponent::Parser);
+        assert_eq!(available[0].installed.as_deref(), Some("v0.9.0"));
+        assert_eq!(available[0].target, "v1.2.0");
+        assert!(!available[0].pinned);
+
+        assert!(
+            manager
+                .upgrade(DataComponent::Base)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            manager.upgrade(DataComponent::Parser).await.unwrap().len(),
+            1
+        );
+        assert_eq!(
+            manager.installed_versions().unwrap()[&DataComponent::Parser],
+            "v1.2.0"
+        );
+        assert!(
+            manager
+                .upgrade(DataComponent::All)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // A pin must belong to the configured model set
+        let mut mismatched = config;
+        mismatched
+            .pinned_versions
+            .insert(DataComponent::Parser, "senzing-v1.1.0".to_string());
+        assert!(
+            DataManager::with_config(mismatched)
+                .ensure_data()
+                .await
+                .is_err()
+        );
+    }
+
+    #[cfg(feature = "runtime-data")]
+    #[tokio::test]
+    async fn test_install_from_archive_dir() {
+        let archives = tempfile::tempdir().unwrap();
+        let archive = archives.path().join(LIBPOSTAL_LANG_CLASS_FILE);
+        write_archive(
+            &archive,
+            &[("language_classifier/language_classifier.dat", b"model")],
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let manager = DataManager::with_config(DataConfig {
+            data_dir: dir.path().to_path_buf(),
+            base_url: "http://127.0.0.1:9/unreachable".to_string(),
+            archive_dir: Some(archives.path().to_path_buf()),
+            ..DataConfig::default()
+        });
+
+        manager
+            .download_component(DataComponent::LanguageClassifier)
+            .await
+            .unwrap();
+
+        let installed = manager
+            .active_data_dir()
+            .join("language_classifier/language_classifier.dat");
+        assert_eq!(std::fs::read(installed).unwrap(), b"model");
+        assert_eq!(
+            manager
+                .check_component_version(DataComponent::LanguageClassifier)
+                .unwrap()
+                .as_deref(),
+            Some(LIBPOSTAL_LANG_CLASS_MODEL_LATEST_VERSION)
+        );
+        assert!(archive.exists(), "local archives are left in place");
+    }
+
+    #[cfg(feature = "runtime-data")]
+    #[tokio::test]
+    async fn test_install_from_file_url() {
+        let archives = tempfile::tempdir().unwrap();
+        let archive = archives.path().join("mirrored-parser.tar.gz");
+        write_archive(&archive, &component_files(DataComponent::Parser));
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = DataConfig {
+            data_dir: dir.path().to_path_buf(),
+            ..DataConfig::default()
+        };
+        let url = reqwest::Url::from_file_path(&archive).unwrap();
+        config
+            .component_urls
+            .insert(DataComponent::Parser, url.to_string());
+        let manager = DataManager::with_config(config);
+
+        manager
+            .download_component(DataComponent::Parser)
+            .await
+            .unwrap();
+        assert!(
+            manager
+                .active_data_dir()
+                .join("address_parser/address_parser_crf.dat")
+                .exists()
+        );
+
+        std::fs::remove_file(&archive).unwrap();
+        std::fs::remove_file(get_version_file_path(
+            DataComponent::Parser,
+            &manager.active_data_dir(),
+        ))
+        .unwrap();
+        assert!(
+            manager
+                .download_component(DataComponent::Parser)
+                .await
+                .is_err()
+        );
+    }
+
+    #[cfg(feature = "runtime-data")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_concurrent_ensure_data_installs_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let archives = tempfile::tempdir().unwrap();
+        for &component in INSTALLED_COMPONENTS {
+            write_archive(
+                &archives.path().join(get_component_info(component).filename),
+                &component_files(component),
+            );
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let extracted = Arc::new(AtomicUsize::new(0));
+        let manager = || {
+            let extracted = Arc::clone(&extracted);
+            DataManager::with_config(DataConfig {
+                data_dir: dir.path().to_path_buf(),
+                archive_dir: Some(archives.path().to_path_buf()),
+                ..DataConfig::default()
+            })
+            .with_progress(ProgressCallback::new(move |progress| {
+                if progress.stage == DownloadStage::Extracting {
+                    extracted.fetch_add(1, Ordering::SeqCst);
+                }
+            }))
+        };
+
+        let (first, second) = (manager(), manager());
+        let (a, b) = tokio::join!(first.ensure_data(), second.ensure_data());
+        a.unwrap();
+        b.unwrap();
+        assert_eq!(extracted.load(Ordering::SeqCst), INSTALLED_COMPONENTS.len());
+
+        // An install waits for the lock and gives up after the timeout
+        let _held = DataLock::try_acquire(dir.path()).unwrap().unwrap();
+        let impatient = DataManager::with_config(DataConfig {
+            data_dir: dir.path().to_path_buf(),
+            lock_timeout_seconds: 0,
+            ..DataConfig::default()
+        });
+        let archive = archives.path().join(LIBPOSTAL_LANG_CLASS_FILE);
+        let err = impatient
+            .import_archive(&archive, DataComponent::LanguageClassifier)
+            .unwrap_err();
+        assert!(err.to_string().contains("Timed out"), "{err}");
+
+        // Installed data is used without waiting for the lock
+        impatient.ensure_data().await.unwrap();
+    }
+
+    #[cfg(feature = "runtime-data")]
+    #[test]
+    fn test_import_directory() {
+        let archives = tempfile::tempdir().unwrap();
+        write_archive(
+            &archives.path().join(LIBPOSTAL_DATA_FILE),
+            &component_files(DataComponent::Base),
+        );
+        write_archive(
+            &archives.path().join(LIBPOSTAL_LANG_CLASS_FILE),
+            &component_files(DataComponent::LanguageClassifier),
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let manager = DataManager::with_data_dir(dir.path().join("data"));
+        let imported = manager.import_directory(archives.path()).unwrap();
+
+        assert_eq!(
+            imported,
+            vec![DataComponent::Base, DataComponent::LanguageClassifier]
+        );
+        assert!(manager.active_data_dir().join("numex/numex.dat").exists());
+        assert!(get_version_file_path(DataComponent::Base, &manager.active_data_dir()).exists());
+        assert!(manager.import_directory(dir.path()).is_err());
+    }
+
+    #[cfg(feature = "runtime-data")]
+    #[test]
+    fn test_import_archive_rejects_wrong_component() {
+        let archives = tempfile::tempdir().unwrap();
+        let archive = archives.path().join(LIBPOSTAL_DATA_FILE);
+        write_archive(&archive, &component_files(DataComponent::Base));
+
+        let dir = tempfile::tempdir().unwrap();
+        let manager = DataManager::with_data_dir(dir.path());
+        let error = manager
+            .import_archive(&archive, DataComponent::Parser)
+            .unwrap_err();
+
+        assert!(
+            error.to_string().contains("is not a libpostal parser"),
+            "{error}"
+        );
+        assert!(!get_version_file_path(DataComponent::Parser, dir.path()).exists());
+        assert!(
+            !dir.path().join("numex").exists(),
+            "entries of other components are skipped"
+        );
+    }
+
+    #[cfg(feature = "runtime-data")]
+    #[test]
+    fn test_import_archive_rejects_path_traversal() {
+        let archives = tempfile::tempdir().unwrap();
+        let archive = archives.path().join(LIBPOSTAL_LANG_CLASS_FILE);
+
+        // tar::Builder refuses `..`, so write the name into the header directly
+        let encoder = flate2::write::GzEncoder::new(
+            std::fs::File::create(&archive).unwrap(),
+            flate2::Compression::fast(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+        let mut header = tar::Header::new_old();
+        let name = b"language_classifier/../../escaped";
+        header.as_old_mut().name[..name.len()].copy_from_slice(name);
+        header.set_size(4);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append(&header, &b"evil"[..]).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = dir.path().join("data");
+        let manager = DataManager::with_data_dir(&data_dir);
+        let error = manager
+            .import_archive(&archive, DataComponent::LanguageClassifier)
+            .unwrap_err();
+
+        assert!(
+            error.to_string().contains("outside the data directory"),
+            "{error}"
+        );
+        assert!(!dir.path().join("escaped").exists());
+    }
+
+    #[cfg(feature = "runtime-data")]
+    #[test]
+    fn test_install_replaces_data_atomically() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = DataManager::with_data_dir(dir.path());
+
+        // Base data installed directly in the data directory, without generations
+        for file in required_files(DataComponent::Base) {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "legacy").unwrap();
+        }
+        assert_eq!(manager.active_data_dir(), dir.path());
+
+        let archives = tempfile::tempdir().unwrap();
+        let archive = archives.path().join(LIBPOSTAL_LANG_CLASS_FILE);
+        let model = "language_classifier/language_classifier.dat";
+        for contents in [&b"first"[..], b"second"] {
+            write_archive(&archive, &[(model, contents)]);
+            manager
+                .import_archive(&archive, DataComponent::LanguageClassifier)
+                .unwrap();
+
+            let active = manager.active_data_dir();
+            assert_ne!(active, dir.path());
+            assert_eq!(std::fs::read(active.join(model)).unwrap(), contents);
+            assert_eq!(
+                std::fs::read(active.join("numex/numex.dat")).unwrap(),
+                b"legacy"
+            );
+        }
+
+        // The previous generation is kept; the legacy layout before it is gone
+        assert!(!dir.path().join("numex").exists());
+
+        // A failed install leaves the current generation in place
+        let active = manager.active_data_dir();
+        write_archive(&archive, &[("language_classifier/other.dat", b"broken")]);
+        assert!(
+            manager
+                .import_archive(&archive, DataComponent::LanguageClassifier)
+                .is_err()
+        );
+        assert_eq!(manager.active_data_dir(), active);
+        assert_eq!(std::fs::read(active.join(model)).unwrap(), b"second");
+        let leftovers = std::fs::read_dir(dir.path())
+            .unwrap()
+            .filter(|entry| {
+                entry
+                    .as_ref()
+                    .unwrap()
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with(".staging-")
+            })
+            .count();
+        assert_eq!(leftovers, 0);
     }
 }
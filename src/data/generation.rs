@@ -0,0 +1,291 @@
+//! Generation directories for crash-safe data installs.
+//!
+//! Installed data lives in numbered generation directories that are never
+//! changed once they are in place:
+//!
+//! ```text
+//! <data_dir>/
+//!     current          # names the active generation, e.g. "generations/3"
+//!     generations/2/   # the previous generation, kept for running processes
+//!     generations/3/   # a complete libpostal data directory
+//!     .staging-…/      # an install in progress
+//! ```
+//!
+//! An install builds a full data directory in a staging directory, syncs it
+//! to disk and renames it into `generations/`. Switching `current` is a
+//! single rename, so a crash at any point leaves either the old or the new
+//! generation active. Data laid out directly in `<data_dir>`, as installed
+//! before generations existed, is used as it is until the next install.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{INSTALLED_COMPONENTS, checksums_file_name, get_component_info, version_file_name};
+
+/// File naming the active generation.
+const CURRENT_FILE: &str = "current";
+
+/// New contents of `current`, renamed over it once written.
+const CURRENT_TEMP_FILE: &str = "current.tmp";
+
+/// Directory holding the generations.
+const GENERATIONS_DIR: &str = "generations";
+
+/// Prefix of staging directories.
+const STAGING_PREFIX: &str = ".staging-";
+
+/// Directory holding the active data.
+///
+/// This is the generation named by `current`, the newest generation if
+/// `current` is missing, or `root` itself for the layout without generations.
+pub(crate) fn active_dir(root: &Path) -> PathBuf {
+    current_generation(root)
+        .or_else(|| generations(root).last().copied())
+        .map_or_else(
+            || root.to_path_buf(),
+            |generation| generation_dir(root, generation),
+        )
+}
+
+/// Create an empty staging directory for an install.
+pub(crate) fn create_staging(root: &Path) -> io::Result<PathBuf> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos());
+    let dir = root.join(format!("{STAGING_PREFIX}{}-{nanos}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Make a complete staging directory the current generation.
+///
+/// Returns the new generation's directory. Generations older than the
+/// previous one are removed afterwards.
+pub(crate) fn publish(root: &Path, staging: &Path) -> io::Result<PathBuf> {
+    sync_tree(staging)?;
+
+    let generations_dir = root.join(GENERATIONS_DIR);
+    fs::create_dir_all(&generations_dir)?;
+    let generation = generations(root).last().map_or(1, |newest| newest + 1);
+    let dir = generation_dir(root, generation);
+    fs::rename(staging, &dir)?;
+    sync_dir(&generations_dir)?;
+
+    set_current(root, generation)?;
+    prune(root)?;
+    Ok(dir)
+}
+
+/// Clean up after an interrupted install.
+///
+/// Removes staging directories and a half-written `current`, points
+/// `current` at the newest generation if it names none, and removes
+/// generations that are neither current nor the one before it.
+pub(crate) fn recover(root: &Path) -> io::Result<()> {
+    if !root.is_dir() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(root)? {
+        let entry = entry?;
+        let staging = entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| name.starts_with(STAGING_PREFIX));
+        if staging {
+            fs::remove_dir_all(entry.path())?;
+        }
+    }
+    remove_file_if_exists(&root.join(CURRENT_TEMP_FILE))?;
+
+    if current_generation(root).is_none()
+        && let Some(&newest) = generations(root).last()
+    {
+        set_current(root, newest)?;
+    }
+    prune(root)
+}
+
+/// Hard-link `source` to `target`, copying where linking fails, recursing into directories.
+pub(crate) fn link_tree(source: &Path, target: &Path) -> io::Result<()> {
+    if source.is_dir() {
+        fs::create_dir_all(target)?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            link_tree(&entry.path(), &target.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::hard_link(source, target).or_else(|_| fs::copy(source, target).map(|_| ()))
+    }
+}
+
+fn generation_dir(root: &Path, generation: u64) -> PathBuf {
+    root.join(GENERATIONS_DIR).join(generation.to_string())
+}
+
+/// Generation named by `current`, if that generation exists.
+fn current_generation(root: &Path) -> Option<u64> {
+    let current = fs::read_to_string(root.join(CURRENT_FILE)).ok()?;
+    let generation = current
+        .trim()
+        .strip_prefix(GENERATIONS_DIR)?
+        .strip_prefix('/')?
+        .parse()
+        .ok()?;
+    generation_dir(root, generation)
+        .is_dir()
+        .then_some(generation)
+}
+
+/// Installed generations, oldest first.
+fn generations(root: &Path) -> Vec<u64> {
+    let Ok(entries) = fs::read_dir(root.join(GENERATIONS_DIR)) else {
+        return Vec::new();
+    };
+    let mut generations: Vec<u64> = entries
+        .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+        .collect();
+    generations.sort_unstable();
+    generations
+}
+
+/// Atomically point `current` at `generation`.
+fn set_current(root: &Path, generation: u64) -> io::Result<()> {
+    let temp = root.join(CURRENT_TEMP_FILE);
+    fs::write(&temp, format!("{GENERATIONS_DIR}/{generation}\n"))?;
+    sync_file(&temp)?;
+    fs::rename(&temp, root.join(CURRENT_FILE))?;
+    sync_dir(root)
+}
+
+/// Remove generations other than the current one and the one before it.
+///
+/// Once a generation precedes the current one, data laid out directly in
+/// `root` is no longer needed either.
+fn prune(root: &Path) -> io::Result<()> {
+    let Some(current) = current_generation(root) else {
+        return Ok(());
+    };
+    let generations = generations(root);
+    let previous = generations.iter().copied().filter(|&g| g < current).max();
+
+    for generation in generations {
+        if generation != current && Some(generation) != previous {
+            fs::remove_dir_all(generation_dir(root, generation))?;
+        }
+    }
+    if previous.is_some() {
+        remove_legacy_layout(root)?;
+    }
+    Ok(())
+}
+
+/// Remove component data kept directly in `root`.
+fn remove_legacy_layout(root: &Path) -> io::Result<()> {
+    for &component in INSTALLED_COMPONENTS {
+        for subdir in get_component_info(component).subdirs {
+            let path = root.join(subdir);
+            if path.is_dir() {
+                fs::remove_dir_all(path)?;
+            }
+        }
+        remove_file_if_exists(&root.join(version_file_name(component)))?;
+        remove_file_if_exists(&root.join(checksums_file_name(component)))?;
+    }
+    Ok(())
+}
+
+fn remove_file_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Flush every file and directory under `dir` to disk.
+fn sync_tree(dir: &Path) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            sync_tree(&path)?;
+        } else {
+            sync_file(&path)?;
+        }
+    }
+    sync_dir(dir)
+}
+
+fn sync_file(path: &Path) -> io::Result<()> {
+    // Windows only flushes handles opened for writing
+    fs::OpenOptions::new()
+        .read(true)
+        .write(cfg!(windows))
+        .open(path)?
+        .sync_all()
+}
+
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    fs::File::open(dir)?.sync_all()?;
+    #[cfg(not(unix))]
+    let _ = dir;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage(root: &Path, marker: &str) -> PathBuf {
+        let staging = create_staging(root).unwrap();
+        fs::write(staging.join("marker"), marker).unwrap();
+        staging
+    }
+
+    #[test]
+    fn test_publish_switches_and_prunes() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+        assert_eq!(active_dir(root), root);
+
+        for marker in ["one", "two", "three"] {
+            let staging = stage(root, marker);
+            let dir = publish(root, &staging).unwrap();
+            assert_eq!(active_dir(root), dir);
+            assert!(!staging.exists());
+        }
+
+        assert_eq!(
+            fs::read_to_string(active_dir(root).join("marker")).unwrap(),
+            "three"
+        );
+        assert_eq!(generations(root), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_recover_interrupted_install() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+        let first = publish(root, &stage(root, "one")).unwrap();
+
+        // Crash after the new generation was renamed into place, before `current` moved
+        let orphan = stage(root, "two");
+        fs::rename(&orphan, generation_dir(root, 2)).unwrap();
+        let leftover = stage(root, "three");
+        fs::write(root.join(CURRENT_TEMP_FILE), "generations/3").unwrap();
+
+        recover(root).unwrap();
+        assert_eq!(active_dir(root), first);
+        assert!(!leftover.exists());
+        assert!(!root.join(CURRENT_TEMP_FILE).exists());
+        assert_eq!(generations(root), vec![1]);
+
+        // A missing pointer falls back to the newest generation
+        fs::remove_file(root.join(CURRENT_FILE)).unwrap();
+        recover(root).unwrap();
+        assert_eq!(current_generation(root), Some(1));
+    }
+}
@@ -0,0 +1,383 @@
+//! SHA-256 integrity checks for libpostal data files.
+//!
+//! Expected checksums come from a [`ChecksumManifest`]: the one compiled into
+//! the crate, an optional manifest file from [`DataConfig`](super::DataConfig),
+//! and the checksums recorded when a component was installed. Verifying a
+//! data directory produces a [`VerificationReport`] listing every file checked.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+use super::{DataComponent, parse_component};
+use crate::error::{Error, Result};
+
+/// Checksums shipped with the crate, see `checksums.txt`.
+const BUILTIN_MANIFEST: &str = include_str!("checksums.txt");
+
+/// Read buffer size used while hashing.
+const HASH_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Expected SHA-256 checksums per data component version.
+#[derive(Debug, Clone, Default)]
+pub struct ChecksumManifest {
+    entries: HashMap<(DataComponent, String, String), String>,
+}
+
+impl ChecksumManifest {
+    /// The manifest compiled into the crate.
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN_MANIFEST).expect("built-in checksum manifest is valid")
+    }
+
+    /// Parse a manifest of `<component> <version> <sha256> <path>` lines.
+    ///
+    /// Blank lines and lines starting with `#` are ignored.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut manifest = Self::default();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [component, version, sha256, path] = fields[..] else {
+                return Err(Error::data_error(format!(
+                    "Checksum manifest line {}: expected `<component> <version> <sha256> <path>`",
+                    number + 1
+                )));
+            };
+            let component = parse_component(component).ok_or_else(|| {
+                Error::data_error(format!(
+                    "Checksum manifest line {}: unknown component {component:?}",
+                    number + 1
+                ))
+            })?;
+            if !is_sha256(sha256) {
+                return Err(Error::data_error(format!(
+                    "Checksum manifest line {}: {sha256:?} is not a SHA-256 digest",
+                    number + 1
+                )));
+            }
+            manifest.insert(component, version, path, sha256);
+        }
+        Ok(manifest)
+    }
+
+    /// Read and parse a manifest file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let text = std::fs::read_to_string(path.as_ref()).map_err(|e| {
+            Error::data_error(format!(
+                "Failed to read checksum manifest {}: {e}",
+                path.as_ref().display()
+            ))
+        })?;
+        Self::parse(&text)
+    }
+
+    /// Add or replace the checksum of `path` for a component version.
+    pub fn insert(&mut self, component: DataComponent, version: &str, path: &str, sha256: &str) {
+        self.entries.insert(
+            (component, version.to_string(), path.to_string()),
+            sha256.to_ascii_lowercase(),
+        );
+    }
+
+    /// Add every entry of `other`, replacing existing ones.
+    pub fn extend(&mut self, other: ChecksumManifest) {
+        self.entries.extend(other.entries);
+    }
+
+    /// Expected checksum of `path` for a component version.
+    pub fn get(&self, component: DataComponent, version: &str, path: &str) -> Option<&str> {
+        self.entries
+            .get(&(component, version.to_string(), path.to_string()))
+            .map(String::as_str)
+    }
+
+    /// Expected checksums of every path listed for a component version.
+    pub fn files(
+        &self,
+        component: DataComponent,
+        version: &str,
+    ) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .filter(move |((c, v, _), _)| *c == component && v == version)
+            .map(|((_, _, path), sha256)| (path.as_str(), sha256.as_str()))
+    }
+
+    /// Whether the manifest has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Outcome of checking one data file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileStatus {
+    /// The checksum matches the expected one
+    Verified,
+    /// The file is present and non-empty but no checksum is known for it
+    Unverified,
+    /// The file does not exist
+    Missing,
+    /// The file is empty
+    Empty,
+    /// The checksum differs from the expected one
+    Mismatch {
+        /// Expected SHA-256, hex encoded
+        expected: String,
+        /// Actual SHA-256, hex encoded
+        actual: String,
+    },
+}
+
+impl FileStatus {
+    /// Whether the file is missing, empty or has the wrong checksum.
+    pub fn is_corrupted(&self) -> bool {
+        matches!(
+            self,
+            FileStatus::Missing | FileStatus::Empty | FileStatus::Mismatch { .. }
+        )
+    }
+}
+
+impl fmt::Display for FileStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FileStatus::Verified => f.write_str("verified"),
+            FileStatus::Unverified => f.write_str("unverified"),
+            FileStatus::Missing => f.write_str("missing"),
+            FileStatus::Empty => f.write_str("empty"),
+            FileStatus::Mismatch { expected, actual } => {
+                write!(f, "checksum mismatch (expected {expected}, got {actual})")
+            }
+        }
+    }
+}
+
+/// Result of checking one data file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileCheck {
+    /// Component the file belongs to
+    pub component: DataComponent,
+    /// Path relative to the data directory
+    pub path: PathBuf,
+    /// What was found
+    pub status: FileStatus,
+}
+
+/// Result of verifying a data directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerificationReport {
+    /// Every file that was checked
+    pub files: Vec<FileCheck>,
+}
+
+impl VerificationReport {
+    /// Whether no file is missing, empty or corrupted.
+    pub fn is_ok(&self) -> bool {
+        !self.files.iter().any(|f| f.status.is_corrupted())
+    }
+
+    /// Files that are missing, empty or corrupted.
+    pub fn corrupted(&self) -> impl Iterator<Item = &FileCheck> {
+        self.files.iter().filter(|f| f.status.is_corrupted())
+    }
+
+    /// Number of files whose checksum matched.
+    pub fn verified(&self) -> usize {
+        self.count(|status| *status == FileStatus::Verified)
+    }
+
+    /// Number of files present but without a known checksum.
+    pub fn unverified(&self) -> usize {
+        self.count(|status| *status == FileStatus::Unverified)
+    }
+
+    fn count(&self, predicate: impl Fn(&FileStatus) -> bool) -> usize {
+        self.files.iter().filter(|f| predicate(&f.status)).count()
+    }
+
+    /// Return the report, or an error naming the corrupted files.
+    pub fn into_result(self) -> Result<Self> {
+        if self.is_ok() {
+            return Ok(self);
+        }
+        let files: Vec<String> = self
+            .corrupted()
+            .map(|f| format!("{} ({})", f.path.display(), f.status))
+            .collect();
+        Err(Error::data_error(format!(
+            "{} corrupted data file(s): {}",
+            files.len(),
+            files.join(", ")
+        )))
+    }
+}
+
+/// Check `path` against `expected`, or only for presence when no checksum is known.
+pub(crate) fn check_file(path: &Path, expected: Option<&str>) -> Result<FileStatus> {
+    let metadata = match std::fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(FileStatus::Missing),
+        Err(e) => {
+            return Err(Error::data_error(format!(
+                "Failed to read metadata for {}: {e}",
+                path.display()
+            )));
+        }
+    };
+    if metadata.len() == 0 {
+        return Ok(FileStatus::Empty);
+    }
+
+    let Some(expected) = expected else {
+        return Ok(FileStatus::Unverified);
+    };
+    let actual = sha256_file(path)?;
+    if actual.eq_ignore_ascii_case(expected) {
+        Ok(FileStatus::Verified)
+    } else {
+        Ok(FileStatus::Mismatch {
+            expected: expected.to_string(),
+            actual,
+        })
+    }
+}
+
+/// SHA-256 of a file's contents, hex encoded.
+pub fn sha256_file<P: AsRef<Path>>(path: P) -> Result<String> {
+    let path = path.as_ref();
+    let mut file = std::fs::File::open(path)
+        .map_err(|e| Error::data_error(format!("Failed to open {}: {e}", path.display())))?;
+
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; HASH_BUFFER_SIZE];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| Error::data_error(format!("Failed to read {}: {e}", path.display())))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hex(&hasher.finalize()))
+}
+
+/// Parse `sha256sum`-style `<sha256>  <path>` lines.
+pub(crate) fn parse_sha256sums(text: &str) -> Vec<(String, String)> {
+    text.lines()
+        .filter_map(|line| {
+            let (sha256, path) = line.split_once(char::is_whitespace)?;
+            let path = path.trim_start().trim_start_matches('*');
+            (is_sha256(sha256) && !path.is_empty())
+                .then(|| (path.to_string(), sha256.to_ascii_lowercase()))
+        })
+        .collect()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn is_sha256(value: &str) -> bool {
+    value.len() == 64 && value.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    #[test]
+    fn test_builtin_manifest_parses() {
+        ChecksumManifest::builtin();
+    }
+
+    // Run `scripts/update-checksums.sh` where the data releases can be
+    // downloaded to fill in the manifest, then drop the `ignore`.
+    #[test]
+    #[ignore = "checksums.txt has no entries until scripts/update-checksums.sh is run"]
+    fn test_builtin_manifest_covers_installed_components() {
+        use crate::data::{INSTALLED_COMPONENTS, ParserModel};
+
+        let manifest = ChecksumManifest::builtin();
+        assert!(!manifest.is_empty());
+        for model in [ParserModel::Default, ParserModel::Senzing] {
+            for &component in INSTALLED_COMPONENTS {
+                let info = model.component_info(component);
+                assert!(
+                    manifest
+                        .get(component, &info.version, &info.filename)
+                        .is_some(),
+                    "no checksum for {component} {} {}",
+                    info.version,
+                    info.filename
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let text = format!("# comment\n\nparser v1.0.0 {ABC_SHA256} parser.tar.gz\n");
+        let manifest = ChecksumManifest::parse(&text).unwrap();
+
+        assert_eq!(
+            manifest.get(DataComponent::Parser, "v1.0.0", "parser.tar.gz"),
+            Some(ABC_SHA256)
+        );
+        assert_eq!(
+            manifest.get(DataComponent::Base, "v1.0.0", "parser.tar.gz"),
+            None
+        );
+        assert!(ChecksumManifest::parse("parser v1.0.0 nothex parser.tar.gz").is_err());
+        assert!(ChecksumManifest::parse(&format!("other v1 {ABC_SHA256} x")).is_err());
+    }
+
+    #[test]
+    fn test_check_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("abc");
+        std::fs::write(&path, "abc").unwrap();
+
+        assert_eq!(sha256_file(&path).unwrap(), ABC_SHA256);
+        assert_eq!(
+            check_file(&path, Some(ABC_SHA256)).unwrap(),
+            FileStatus::Verified
+        );
+        assert_eq!(check_file(&path, None).unwrap(), FileStatus::Unverified);
+        assert!(
+            check_file(&path, Some(&"0".repeat(64)))
+                .unwrap()
+                .is_corrupted()
+        );
+        assert_eq!(
+            check_file(&dir.path().join("missing"), None).unwrap(),
+            FileStatus::Missing
+        );
+    }
+
+    #[test]
+    fn test_report_into_result() {
+        let report = VerificationReport {
+            files: vec![FileCheck {
+                component: DataComponent::Base,
+                path: PathBuf::from("numex/numex.dat"),
+                status: FileStatus::Empty,
+            }],
+        };
+
+        assert!(!report.is_ok());
+        let error = report.into_result().unwrap_err().to_string();
+        assert!(error.contains("numex/numex.dat (empty)"), "{error}");
+    }
+}
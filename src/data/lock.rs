@@ -0,0 +1,102 @@
+//! Advisory lock serializing changes to a data directory.
+//!
+//! The lock is held on `<data_dir>/.lock` with the operating system's file
+//! locking, so it is released when the holder exits, however it exits. Every
+//! acquisition opens the file anew, which makes the lock exclusive between
+//! threads of one process as well as between processes.
+
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Lock file in the data directory.
+pub(crate) const LOCK_FILE: &str = ".lock";
+
+/// How often a waiting caller retries the lock.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Exclusive lock on a data directory, released when dropped.
+#[derive(Debug)]
+pub(crate) struct DataLock {
+    _file: File,
+}
+
+impl DataLock {
+    /// Take the lock on `root` if nobody else holds it.
+    pub(crate) fn try_acquire(root: &Path) -> io::Result<Option<Self>> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(root.join(LOCK_FILE))?;
+        match file.try_lock() {
+            Ok(()) => Ok(Some(Self { _file: file })),
+            Err(TryLockError::WouldBlock) => Ok(None),
+            Err(TryLockError::Error(e)) => Err(e),
+        }
+    }
+
+    /// Take the lock on `root`, waiting up to `timeout` for its holder.
+    ///
+    /// Returns `None` if the lock is still held when `timeout` runs out.
+    pub(crate) fn acquire(root: &Path, timeout: Duration) -> io::Result<Option<Self>> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(lock) = Self::try_acquire(root)? {
+                return Ok(Some(lock));
+            }
+            if Instant::now() >= deadline {
+                return Ok(None);
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Like [`acquire`](Self::acquire), without blocking the async runtime while waiting.
+    #[cfg(feature = "runtime-data")]
+    pub(crate) async fn acquire_async(root: &Path, timeout: Duration) -> io::Result<Option<Self>> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(lock) = Self::try_acquire(root)? {
+                return Ok(Some(lock));
+            }
+            if Instant::now() >= deadline {
+                return Ok(None);
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_excludes_other_threads() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().to_path_buf();
+        let lock = DataLock::try_acquire(&root).unwrap().unwrap();
+
+        let waiter = {
+            let root = root.clone();
+            std::thread::spawn(move || {
+                assert!(DataLock::try_acquire(&root).unwrap().is_none());
+                assert!(
+                    DataLock::acquire(&root, Duration::from_millis(200))
+                        .unwrap()
+                        .is_none()
+                );
+                DataLock::acquire(&root, Duration::from_secs(10))
+                    .unwrap()
+                    .is_some()
+            })
+        };
+
+        std::thread::sleep(Duration::from_millis(500));
+        drop(lock);
+        assert!(waiter.join().unwrap());
+    }
+}
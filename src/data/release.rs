@@ -0,0 +1,150 @@
+//! Data versions available for install, and updates to them.
+//!
+//! A [`ReleaseManifest`] names the version of each component to roll out.
+//! Versions are written the way version files record them: `v1.0.0` for the
+//! default model set and `senzing-v1.1.0` for the Senzing one, so a single
+//! manifest serves both. The built-in manifest holds the versions this crate
+//! was released with; [`DataConfig`](super::DataConfig) can point at a newer
+//! one, locally or on a server.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::{DataComponent, INSTALLED_COMPONENTS, ParserModel, parse_component};
+use crate::error::{Error, Result};
+
+/// Versions of each component to install, per model set.
+#[derive(Debug, Clone, Default)]
+pub struct ReleaseManifest {
+    versions: HashMap<(DataComponent, ParserModel), String>,
+}
+
+impl ReleaseManifest {
+    /// The versions this crate installs by default.
+    pub fn builtin() -> Self {
+        let mut manifest = Self::default();
+        for model in [ParserModel::Default, ParserModel::Senzing] {
+            for &component in INSTALLED_COMPONENTS {
+                manifest.insert(component, &model.component_info(component).version);
+            }
+        }
+        manifest
+    }
+
+    /// Parse a manifest of `<component> <version>` lines.
+    ///
+    /// Blank lines and lines starting with `#` are ignored. A later line for
+    /// the same component and model set replaces an earlier one.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut manifest = Self::default();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [component, version] = fields[..] else {
+                return Err(Error::data_error(format!(
+                    "Release manifest line {}: expected `<component> <version>`",
+                    number + 1
+                )));
+            };
+            let component = parse_component(component).ok_or_else(|| {
+                Error::data_error(format!(
+                    "Release manifest line {}: unknown component {component:?}",
+                    number + 1
+                ))
+            })?;
+            manifest.insert(component, version);
+        }
+        Ok(manifest)
+    }
+
+    /// Read and parse a manifest file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let text = std::fs::read_to_string(path.as_ref()).map_err(|e| {
+            Error::data_error(format!(
+                "Failed to read release manifest {}: {e}",
+                path.as_ref().display()
+            ))
+        })?;
+        Self::parse(&text)
+    }
+
+    /// Set the version of a component for the model set the version belongs to.
+    pub fn insert(&mut self, component: DataComponent, version: &str) {
+        self.versions.insert(
+            (component, ParserModel::from_version(version)),
+            version.to_string(),
+        );
+    }
+
+    /// Add every entry of `other`, replacing existing ones.
+    pub fn extend(&mut self, other: ReleaseManifest) {
+        self.versions.extend(other.versions);
+    }
+
+    /// Version of a component to install for a model set.
+    pub fn version(&self, component: DataComponent, model: ParserModel) -> Option<&str> {
+        self.versions.get(&(component, model)).map(String::as_str)
+    }
+}
+
+/// Installed and wanted version of one component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentUpdate {
+    /// The component
+    pub component: DataComponent,
+    /// Installed version, if the component is installed with a version file
+    pub installed: Option<String>,
+    /// Version to install: the pinned one, or the release manifest's
+    pub target: String,
+    /// Whether `target` comes from `DataConfig::pinned_versions`
+    pub pinned: bool,
+}
+
+impl ComponentUpdate {
+    /// Whether installing `target` would change the installed version.
+    ///
+    /// This includes rolling back to an older `target`.
+    pub fn is_available(&self) -> bool {
+        self.installed.as_deref() != Some(self.target.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let mut manifest = ReleaseManifest::builtin();
+        manifest.extend(
+            ReleaseManifest::parse(
+                "# rollout\n\nparser v1.2.0\nparser senzing-v1.2.0\nbase v1.0.1\nbase v1.0.2\n",
+            )
+            .unwrap(),
+        );
+
+        assert_eq!(
+            manifest.version(DataComponent::Parser, ParserModel::Default),
+            Some("v1.2.0")
+        );
+        assert_eq!(
+            manifest.version(DataComponent::Parser, ParserModel::Senzing),
+            Some("senzing-v1.2.0")
+        );
+        assert_eq!(
+            manifest.version(DataComponent::Base, ParserModel::Default),
+            Some("v1.0.2")
+        );
+        assert_eq!(
+            manifest.version(DataComponent::LanguageClassifier, ParserModel::Senzing),
+            Some("senzing-v1.1.0")
+        );
+
+        assert!(ReleaseManifest::parse("parser").is_err());
+        assert!(ReleaseManifest::parse("all v1.0.0").is_err());
+    }
+}
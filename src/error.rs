@@ -1,5 +1,10 @@
 //! Error types and handling for libpostal-rs.
 
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::ffi::SetupCall;
+
 /// Result type alias for libpostal operations.
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -11,6 +16,25 @@ pub enum Error {
     InitializationFailed {
         /// Error message from libpostal
         message: String,
+        /// Underlying cause, such as the setup call that failed
+        #[source]
+        source: Option<Arc<Error>>,
+    },
+
+    /// A libpostal setup function reported failure
+    #[error("{call} failed for data directory {}", data_dir.display())]
+    SetupFailed {
+        /// The setup function that failed
+        call: SetupCall,
+        /// Data directory it was called with
+        data_dir: PathBuf,
+    },
+
+    /// An API was used whose libpostal subsystem is not enabled
+    #[error("Subsystem not enabled: {message}")]
+    SubsystemDisabled {
+        /// Error message
+        message: String,
     },
 
     /// Data management errors
@@ -56,6 +80,22 @@ pub enum Error {
         /// Error message
         message: String,
     },
+
+    /// CSV reading or writing errors
+    #[cfg(feature = "csv")]
+    #[error("CSV error: {message}")]
+    CsvError {
+        /// Error message
+        message: String,
+    },
+
+    /// Arrow or Parquet conversion errors
+    #[cfg(feature = "arrow")]
+    #[error("Arrow error: {message}")]
+    ArrowError {
+        /// Error message
+        message: String,
+    },
 }
 
 impl Error {
@@ -63,6 +103,30 @@ impl Error {
     pub fn initialization_failed(message: impl Into<String>) -> Self {
         Self::InitializationFailed {
             message: message.into(),
+            source: None,
+        }
+    }
+
+    /// Create a new initialization error caused by `source`
+    pub fn initialization_failed_from(message: impl Into<String>, source: Arc<Error>) -> Self {
+        Self::InitializationFailed {
+            message: message.into(),
+            source: Some(source),
+        }
+    }
+
+    /// Create a new setup failure for `call` against `data_dir`
+    pub fn setup_failed(call: SetupCall, data_dir: impl Into<PathBuf>) -> Self {
+        Self::SetupFailed {
+            call,
+            data_dir: data_dir.into(),
+        }
+    }
+
+    /// Create a new subsystem disabled error
+    pub fn subsystem_disabled(message: impl Into<String>) -> Self {
+        Self::SubsystemDisabled {
+            message: message.into(),
         }
     }
 
@@ -101,4 +165,42 @@ impl Error {
             message: message.into(),
         }
     }
+
+    /// Create a new CSV error
+    #[cfg(feature = "csv")]
+    pub fn csv_error(message: impl Into<String>) -> Self {
+        Self::CsvError {
+            message: message.into(),
+        }
+    }
+
+    /// Create a new Arrow error
+    #[cfg(feature = "arrow")]
+    pub fn arrow_error(message: impl Into<String>) -> Self {
+        Self::ArrowError {
+            message: message.into(),
+        }
+    }
+
+    /// Short name of the error's variant, such as `parse_error`
+    ///
+    /// Stable across releases, for grouping errors in metrics and logs.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::InitializationFailed { .. } => "initialization_failed",
+            Self::SetupFailed { .. } => "setup_failed",
+            Self::SubsystemDisabled { .. } => "subsystem_disabled",
+            Self::DataError { .. } => "data_error",
+            Self::ParseError { .. } => "parse_error",
+            Self::NormalizationError { .. } => "normalization_error",
+            Self::FfiError { .. } => "ffi_error",
+            Self::IoError { .. } => "io_error",
+            #[cfg(feature = "runtime-data")]
+            Self::NetworkError { .. } => "network_error",
+            #[cfg(feature = "csv")]
+            Self::CsvError { .. } => "csv_error",
+            #[cfg(feature = "arrow")]
+            Self::ArrowError { .. } => "arrow_error",
+        }
+    }
 }
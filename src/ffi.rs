@@ -20,12 +20,14 @@
 //!
 //! The libpostal C library has specific threading requirements that this module respects:
 //!
-//! * **Initialization**: `libpostal_setup()` must be called exactly once per process before
-//!   any other libpostal functions. This is handled thread-safely using `std::sync::Once`.
+//! * **Initialization**: `libpostal_setup()` must be called before any other libpostal
+//!   functions. Setup runs under the write side of a global `RwLock`.
 //! * **Concurrent Usage**: Once initialized, the parsing and normalization functions are
-//!   thread-safe and can be called concurrently from multiple threads.
-//! * **Teardown**: `libpostal_teardown()` should be called at process exit, but is optional
-//!   as the OS will clean up resources anyway.
+//!   thread-safe and can be called concurrently from multiple threads. Each call holds
+//!   the read side of the lock.
+//! * **Teardown**: `libpostal_teardown()` runs when the last `LibPostal` handle is dropped,
+//!   after every in-flight call has finished. `LibPostal::reload` tears down and sets up
+//!   again the same way.
 //!
 //! # Error Handling Strategy
 //!
@@ -61,9 +63,13 @@
 
 #![allow(missing_docs)] // Generated bindings don't have docs
 
+use crate::data::{self, ParserModel};
 use crate::error::{Error, Result};
+use crate::types::Subsystems;
 use std::ffi::{CStr, CString};
-use std::sync::Once;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 // Include generated bindings
 #[allow(non_upper_case_globals)]
@@ -77,20 +83,323 @@ mod bindings {
 
 pub use bindings::*;
 
-// Global initialization state
-static INIT: Once = Once::new();
-static mut INITIALIZED: bool = false;
-static mut INIT_ERROR_MSG: [u8; 256] = [0; 256];
-static mut INIT_ERROR_LEN: usize = 0;
+// Global initialization state.
+//
+// `LIFECYCLE` is held for reading by every in-flight libpostal call and for
+// writing while libpostal is set up, torn down or reloaded, so teardown never
+// races a parse.
+static LIFECYCLE: RwLock<Lifecycle> = RwLock::new(Lifecycle::new());
+
+/// A libpostal setup function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupCall {
+    /// `libpostal_setup_datadir`, the base data every API needs
+    Base,
+    /// `libpostal_setup_parser_datadir`, the address parser model
+    Parser,
+    /// `libpostal_setup_language_classifier_datadir`, the language classifier model
+    LanguageClassifier,
+}
+
+impl SetupCall {
+    /// Name of the C function.
+    pub fn function_name(self) -> &'static str {
+        match self {
+            SetupCall::Base => "libpostal_setup_datadir",
+            SetupCall::Parser => "libpostal_setup_parser_datadir",
+            SetupCall::LanguageClassifier => "libpostal_setup_language_classifier_datadir",
+        }
+    }
+}
+
+impl fmt::Display for SetupCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.function_name())
+    }
+}
+
+/// State of the process-wide libpostal setup.
+#[derive(Debug, Clone)]
+pub enum InitState {
+    /// libpostal has not been set up, or was torn down
+    Uninitialized,
+    /// libpostal is set up and usable
+    Ready {
+        /// Subsystems whose data is loaded; enabled models load on first use
+        subsystems: Subsystems,
+        /// Data directory libpostal was set up from
+        data_dir: PathBuf,
+        /// Model set installed in `data_dir`
+        parser_model: ParserModel,
+    },
+    /// Setting up the base data or a model failed
+    Failed(Arc<Error>),
+}
+
+impl InitState {
+    /// Whether libpostal is set up and usable.
+    pub fn is_ready(&self) -> bool {
+        matches!(self, InitState::Ready { .. })
+    }
+}
+
+/// Why a setup step failed; turned into an [`Error::InitializationFailed`] on use.
+#[derive(Debug, Clone)]
+struct SetupFailure {
+    message: String,
+    cause: Arc<Error>,
+}
+
+impl SetupFailure {
+    fn to_error(&self) -> Error {
+        Error::initialization_failed_from(self.message.clone(), Arc::clone(&self.cause))
+    }
+}
+
+type SetupResult = std::result::Result<(), SetupFailure>;
+
+/// Process-wide libpostal setup state.
+struct Lifecycle {
+    /// Number of live `LibPostal` handles
+    handles: usize,
+    /// Whether libpostal was torn down when its last handle was released
+    released: bool,
+    /// Data directory libpostal is, or was last, set up from
+    data_dir: Option<PathBuf>,
+    /// Model set installed in `data_dir`
+    parser_model: ParserModel,
+    /// How many live handles enabled each subsystem
+    enabled_by: SubsystemCounts,
+    /// Outcome of the base setup since the last teardown
+    base: Option<SetupResult>,
+    /// Outcome of loading the parser model, once attempted
+    parser: Option<SetupResult>,
+    /// Outcome of loading the language classifier, once attempted
+    classifier: Option<SetupResult>,
+}
+
+impl Lifecycle {
+    const fn new() -> Self {
+        Self {
+            handles: 0,
+            released: false,
+            data_dir: None,
+            parser_model: ParserModel::Default,
+            enabled_by: SubsystemCounts::NONE,
+            base: None,
+            parser: None,
+            classifier: None,
+        }
+    }
+
+    fn is_set_up(&self) -> bool {
+        self.base.is_some()
+    }
+
+    /// Run the base setup from `data_dir` unless it already ran.
+    fn ensure_setup(&mut self, data_dir: &Path) {
+        if !self.is_set_up() {
+            self.data_dir = Some(data_dir.to_path_buf());
+            self.parser_model = data::installed_parser_model(data_dir);
+            self.base = Some(setup(data_dir));
+        }
+    }
+
+    /// Count a new handle that enables `subsystems`.
+    fn register(&mut self, subsystems: Subsystems) {
+        self.handles += 1;
+        self.released = false;
+        self.enabled_by.add(subsystems);
+    }
+
+    /// Drop a handle registered with `subsystems`, tearing libpostal down
+    /// when it was the last one.
+    fn unregister(&mut self, subsystems: Subsystems) {
+        self.handles = self.handles.saturating_sub(1);
+        self.enabled_by.remove(subsystems);
+        if self.handles == 0 {
+            self.teardown();
+            self.released = true;
+        }
+    }
+
+    /// Subsystems callers may use: those enabled by any live handle.
+    ///
+    /// Without handles, libpostal set up implicitly by a standalone parser
+    /// or normalizer may use every subsystem.
+    fn enabled(&self) -> Subsystems {
+        if self.handles > 0 {
+            self.enabled_by.enabled()
+        } else if self.is_set_up() {
+            Subsystems::ALL
+        } else {
+            Subsystems::NONE
+        }
+    }
+
+    /// Fail with `Error::SubsystemDisabled` unless `subsystem` may be used.
+    fn check_enabled(&self, subsystem: Subsystem) -> Result<()> {
+        if subsystem.is_enabled(self.enabled()) {
+            return Ok(());
+        }
+        Err(Error::subsystem_disabled(format!(
+            "{} is not enabled; set LibPostalConfigBuilder::{}(true) to use it",
+            subsystem.describe(),
+            subsystem.config_option()
+        )))
+    }
+
+    /// Refuse to set libpostal up implicitly once its last handle released it.
+    ///
+    /// Parsers and normalizers that outlive every `LibPostal` would otherwise
+    /// load the data again, with no handle left to ever tear it down.
+    fn check_implicit_setup(&self) -> Result<()> {
+        if self.released {
+            return Err(Error::initialization_failed(
+                "libpostal was torn down when the last LibPostal was dropped; create a new LibPostal to use it again",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Forget a failed setup that no handle depends on, so the next setup
+    /// tries again, possibly from another data directory.
+    fn clear_failure(&mut self) {
+        if self.handles == 0 && matches!(self.state(), InitState::Failed(_)) {
+            self.teardown();
+        }
+    }
+
+    /// Result of the base setup.
+    fn base_result(&self) -> Result<()> {
+        match &self.base {
+            Some(Ok(())) => Ok(()),
+            Some(Err(failure)) => Err(failure.to_error()),
+            None => Err(Error::initialization_failed("libpostal is not set up")),
+        }
+    }
+
+    fn model(&mut self, subsystem: Subsystem) -> &mut Option<SetupResult> {
+        match subsystem {
+            Subsystem::Parser => &mut self.parser,
+            Subsystem::LanguageClassifier => &mut self.classifier,
+            Subsystem::Expand => unreachable!("expansion has no separate model"),
+        }
+    }
+
+    fn state(&self) -> InitState {
+        let Some(base) = &self.base else {
+            return InitState::Uninitialized;
+        };
+        let failure = [
+            base,
+            self.parser.as_ref().unwrap_or(&Ok(())),
+            self.classifier.as_ref().unwrap_or(&Ok(())),
+        ]
+        .into_iter()
+        .find_map(|result| result.as_ref().err());
+        if let Some(failure) = failure {
+            return InitState::Failed(Arc::new(failure.to_error()));
+        }
+
+        InitState::Ready {
+            subsystems: Subsystems {
+                expand: self.enabled().expand,
+                parser: matches!(self.parser, Some(Ok(()))),
+                language_classifier: matches!(self.classifier, Some(Ok(()))),
+            },
+            data_dir: self.data_dir.clone().unwrap_or_default(),
+            parser_model: self.parser_model,
+        }
+    }
+
+    /// Tear down every loaded model and the base data.
+    fn teardown(&mut self) {
+        if matches!(self.base, Some(Ok(()))) {
+            unsafe {
+                if matches!(self.parser, Some(Ok(()))) {
+                    libpostal_teardown_parser();
+                }
+                if matches!(self.classifier, Some(Ok(()))) {
+                    libpostal_teardown_language_classifier();
+                }
+                libpostal_teardown();
+            }
+        }
+        self.base = None;
+        self.parser = None;
+        self.classifier = None;
+    }
+}
+
+/// How many live handles enabled each subsystem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SubsystemCounts {
+    expand: usize,
+    parser: usize,
+    language_classifier: usize,
+}
+
+impl SubsystemCounts {
+    const NONE: Self = Self {
+        expand: 0,
+        parser: 0,
+        language_classifier: 0,
+    };
+
+    fn add(&mut self, subsystems: Subsystems) {
+        self.expand += usize::from(subsystems.expand);
+        self.parser += usize::from(subsystems.parser);
+        self.language_classifier += usize::from(subsystems.language_classifier);
+    }
+
+    fn remove(&mut self, subsystems: Subsystems) {
+        self.expand = self.expand.saturating_sub(usize::from(subsystems.expand));
+        self.parser = self.parser.saturating_sub(usize::from(subsystems.parser));
+        self.language_classifier = self
+            .language_classifier
+            .saturating_sub(usize::from(subsystems.language_classifier));
+    }
+
+    fn enabled(self) -> Subsystems {
+        Subsystems {
+            expand: self.expand > 0,
+            parser: self.parser > 0,
+            language_classifier: self.language_classifier > 0,
+        }
+    }
+}
+
+fn read_lifecycle() -> RwLockReadGuard<'static, Lifecycle> {
+    LIFECYCLE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+fn write_lifecycle() -> RwLockWriteGuard<'static, Lifecycle> {
+    LIFECYCLE.write().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Current state of the process-wide libpostal setup.
+pub(crate) fn status() -> InitState {
+    read_lifecycle().state()
+}
 
 /// Initialize libpostal in a thread-safe manner.
 ///
-/// This function ensures that `libpostal_setup()` is called exactly once per process,
-/// using `std::sync::Once` for thread safety. Subsequent calls will return immediately.
+/// This function ensures that `libpostal_setup()` has run, setting libpostal
+/// up under the global write lock if needed. Subsequent calls return
+/// immediately.
+///
+/// If libpostal has not been set up yet, the data directory it was last set
+/// up from is used, or the default data directory. Use
+/// [`initialize_with_data_dir`] to set it up from a specific directory.
+///
+/// Once the last `LibPostal` handle has been dropped and libpostal torn
+/// down, this fails instead of setting it up again; registering a new
+/// handle makes it usable once more.
 ///
 /// # Memory Safety
 ///
-/// - Thread-safe initialization using `std::sync::Once`
+/// - Thread-safe initialization behind a global `RwLock`
 /// - Idempotent: safe to call multiple times
 /// - No memory allocation on subsequent calls
 ///
@@ -110,111 +419,278 @@ static mut INIT_ERROR_LEN: usize = 0;
 /// initialize().expect("Failed to initialize libpostal");
 /// ```
 pub(crate) fn initialize() -> Result<()> {
-    INIT.call_once(|| {
-        // Try to use data directory if available
-        let data_manager = crate::data::DataManager::new();
-        let success = if data_manager.is_data_available() {
-            let data_dir = data_manager.data_dir();
-            match std::ffi::CString::new(data_dir.to_string_lossy().as_ref()) {
-                Ok(c_data_dir) => unsafe {
-                    // Set up libpostal with data directory
-                    let setup_result = libpostal_setup_datadir(c_data_dir.as_ptr() as *mut _);
-                    let parser_result =
-                        libpostal_setup_parser_datadir(c_data_dir.as_ptr() as *mut _);
-                    let classifier_result =
-                        libpostal_setup_language_classifier_datadir(c_data_dir.as_ptr() as *mut _);
-
-                    // All three need to succeed for full functionality
-                    setup_result && parser_result && classifier_result
-                },
-                Err(_) => {
-                    unsafe {
-                        let msg = b"Invalid data directory path";
-                        INIT_ERROR_LEN = msg.len().min(255);
-                        INIT_ERROR_MSG[..INIT_ERROR_LEN].copy_from_slice(&msg[..INIT_ERROR_LEN]);
-                    }
-                    false
-                }
-            }
-        } else {
-            // Fall back to default setup (will likely fail without data)
-            unsafe {
-                let setup_result = libpostal_setup();
-                let parser_result = libpostal_setup_parser();
-                let classifier_result = libpostal_setup_language_classifier();
+    if !read_lifecycle().is_set_up() {
+        let mut lifecycle = write_lifecycle();
+        lifecycle.check_implicit_setup()?;
+        let data_dir = lifecycle
+            .data_dir
+            .clone()
+            .unwrap_or_else(|| crate::data::DataManager::new().active_data_dir());
+        lifecycle.ensure_setup(&data_dir);
+    }
+    read_lifecycle().base_result()
+}
 
-                setup_result && parser_result && classifier_result
-            }
-        };
+/// Register a new `LibPostal` handle, setting libpostal up from `data_dir`.
+///
+/// libpostal keeps its models in process-wide state, so it can only be set
+/// up from one data directory at a time. While it is set up, calling this
+/// with the same directory only counts the new handle.
+///
+/// Only the base data is loaded here. The parser and language classifier
+/// models in `subsystems` are loaded the first time they are used; models
+/// for subsystems that no caller enabled are never loaded.
+///
+/// Every successful call must be paired with a call to [`release`]. A failed
+/// setup is tried again by the next call once no handle is left.
+///
+/// # Errors
+///
+/// Returns `Error::InitializationFailed` if libpostal setup fails, or if
+/// libpostal is already set up from a different data directory.
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(data_dir = %data_dir.display())))]
+pub(crate) fn initialize_with_data_dir(data_dir: &Path, subsystems: Subsystems) -> Result<()> {
+    let mut lifecycle = write_lifecycle();
+    // The data may have been installed since an earlier attempt failed
+    lifecycle.clear_failure();
+    lifecycle.ensure_setup(data_dir);
+
+    if let Some(active) = &lifecycle.data_dir
+        && !same_path(active, data_dir)
+    {
+        return Err(Error::initialization_failed(format!(
+            "libpostal is already set up with data directory {}; cannot switch to {} in the same process",
+            active.display(),
+            data_dir.display()
+        )));
+    }
+    lifecycle.base_result()?;
 
-        if success {
-            unsafe {
-                INITIALIZED = true;
-            }
-        } else {
-            let error_msg = if !data_manager.is_data_available() {
-                "libpostal initialization failed - data files not found. Run data download first."
-            } else {
-                "libpostal initialization failed"
+    lifecycle.register(subsystems);
+    Ok(())
+}
+
+/// Drop a handle registered with [`initialize_with_data_dir`] for `subsystems`.
+///
+/// When the last handle goes away, libpostal is torn down once every
+/// in-flight call has finished, and stays down until a new handle is
+/// registered.
+pub(crate) fn release(subsystems: Subsystems) {
+    write_lifecycle().unregister(subsystems);
+}
+
+/// Tear libpostal down and set it up again from `data_dir`.
+///
+/// Waits for in-flight calls to finish and blocks new ones until the base
+/// data has been reloaded. Models are loaded again lazily on first use.
+///
+/// # Errors
+///
+/// Returns `Error::InitializationFailed` if setup from `data_dir` fails. libpostal
+/// then stays unusable until a later reload succeeds.
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(data_dir = %data_dir.display())))]
+pub(crate) fn reload(data_dir: &Path) -> Result<()> {
+    let mut lifecycle = write_lifecycle();
+    lifecycle.teardown();
+    lifecycle.ensure_setup(data_dir);
+    lifecycle.base_result()
+}
+
+/// Subsystems needed by the FFI entry points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Subsystem {
+    Expand,
+    Parser,
+    LanguageClassifier,
+}
+
+impl Subsystem {
+    fn is_enabled(self, enabled: Subsystems) -> bool {
+        match self {
+            Subsystem::Expand => enabled.expand,
+            Subsystem::Parser => enabled.parser,
+            Subsystem::LanguageClassifier => enabled.language_classifier,
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            Subsystem::Expand => "address expansion",
+            Subsystem::Parser => "the address parser",
+            Subsystem::LanguageClassifier => "the language classifier",
+        }
+    }
+
+    fn setup_call(self) -> SetupCall {
+        match self {
+            Subsystem::Expand => SetupCall::Base,
+            Subsystem::Parser => SetupCall::Parser,
+            Subsystem::LanguageClassifier => SetupCall::LanguageClassifier,
+        }
+    }
+
+    fn config_option(self) -> &'static str {
+        match self {
+            Subsystem::Expand => "enable_expand",
+            Subsystem::Parser => "enable_parser",
+            Subsystem::LanguageClassifier => "enable_language_classifier",
+        }
+    }
+}
+
+/// Make sure `subsystems` are enabled and loaded, and hold libpostal open.
+///
+/// The returned guard must be kept alive for the whole libpostal call so a
+/// concurrent teardown or reload waits for it.
+fn require(subsystems: &[Subsystem]) -> Result<RwLockReadGuard<'static, Lifecycle>> {
+    'retry: loop {
+        initialize()?;
+
+        let lifecycle = read_lifecycle();
+        if !lifecycle.is_set_up() {
+            // Torn down between `initialize` and here; set up again
+            continue;
+        }
+        lifecycle.base_result()?;
+
+        for &subsystem in subsystems {
+            lifecycle.check_enabled(subsystem)?;
+
+            let loaded = match subsystem {
+                Subsystem::Expand => &Some(Ok(())),
+                Subsystem::Parser => &lifecycle.parser,
+                Subsystem::LanguageClassifier => &lifecycle.classifier,
             };
-            unsafe {
-                INITIALIZED = false;
-                if INIT_ERROR_LEN == 0 {
-                    let msg_bytes = error_msg.as_bytes();
-                    INIT_ERROR_LEN = msg_bytes.len().min(255);
-                    INIT_ERROR_MSG[..INIT_ERROR_LEN].copy_from_slice(&msg_bytes[..INIT_ERROR_LEN]);
+            match loaded {
+                Some(Ok(())) => {}
+                Some(Err(failure)) => return Err(failure.to_error()),
+                None => {
+                    drop(lifecycle);
+                    let mut lifecycle = write_lifecycle();
+                    if lifecycle.is_set_up() && lifecycle.model(subsystem).is_none() {
+                        let result = setup_model(subsystem, lifecycle.data_dir.as_deref());
+                        *lifecycle.model(subsystem) = Some(result);
+                    }
+                    continue 'retry;
                 }
             }
         }
-    });
+        return Ok(lifecycle);
+    }
+}
 
-    unsafe {
-        if INITIALIZED {
-            Ok(())
-        } else {
-            let error_msg = if INIT_ERROR_LEN > 0 {
-                std::str::from_utf8(&INIT_ERROR_MSG[..INIT_ERROR_LEN])
-                    .unwrap_or("libpostal initialization failed")
-            } else {
-                "libpostal initialization failed"
-            };
-            Err(Error::initialization_failed(error_msg))
+/// Load the model for `subsystem` from the data directory libpostal was set up with.
+#[cfg_attr(feature = "tracing", tracing::instrument(skip(data_dir)))]
+fn setup_model(subsystem: Subsystem, data_dir: Option<&Path>) -> SetupResult {
+    let data_dir = data_dir.unwrap_or(Path::new(""));
+    let c_data_dir = available_data_dir(data_dir)?;
+
+    #[cfg(feature = "tracing")]
+    let started = std::time::Instant::now();
+    let success = unsafe {
+        match (subsystem, &c_data_dir) {
+            (Subsystem::Parser, Some(dir)) => {
+                libpostal_setup_parser_datadir(dir.as_ptr() as *mut _)
+            }
+            (Subsystem::Parser, None) => libpostal_setup_parser(),
+            (Subsystem::LanguageClassifier, Some(dir)) => {
+                libpostal_setup_language_classifier_datadir(dir.as_ptr() as *mut _)
+            }
+            (Subsystem::LanguageClassifier, None) => libpostal_setup_language_classifier(),
+            (Subsystem::Expand, _) => true,
         }
+    };
+    #[cfg(feature = "tracing")]
+    tracing::info!(
+        success,
+        duration_ms = started.elapsed().as_millis() as u64,
+        "loaded model"
+    );
+
+    if success {
+        Ok(())
+    } else {
+        Err(call_failed(
+            format!("failed to load {}", subsystem.describe()),
+            subsystem.setup_call(),
+            data_dir,
+            c_data_dir.is_some(),
+        ))
     }
 }
 
-/// Clean up libpostal resources.
-///
-/// This function calls `libpostal_teardown()` to clean up any resources
-/// allocated by libpostal. It's generally not necessary to call this as
-/// the OS will clean up process resources on exit.
-///
-/// # Safety
-///
-/// This function should only be called when you're certain no other threads
-/// are using libpostal functions. Calling this while other threads are active
-/// may lead to undefined behavior.
-///
-/// # Memory Safety
-///
-/// - Safe to call even if libpostal was never initialized
-/// - No-op if called multiple times
-/// - Frees internal C library resources
-///
-/// # Note
-///
-/// Currently unused but provided for completeness. Most applications should
-/// rely on OS cleanup at process exit rather than explicit teardown.
-#[allow(dead_code)]
-pub(crate) fn teardown() -> Result<()> {
-    unsafe {
-        if INITIALIZED {
-            libpostal_teardown();
-            libpostal_teardown_parser();
-            libpostal_teardown_language_classifier();
+/// Set up the libpostal base data from `data_dir`. Must only run with the write lock held.
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(data_dir = %data_dir.display())))]
+fn setup(data_dir: &Path) -> SetupResult {
+    let c_data_dir = available_data_dir(data_dir)?;
+
+    #[cfg(feature = "tracing")]
+    let started = std::time::Instant::now();
+    let success = unsafe {
+        match &c_data_dir {
+            // Set up the base data; models are loaded on first use
+            Some(dir) => libpostal_setup_datadir(dir.as_ptr() as *mut _),
+            // Fall back to default setup (will likely fail without data)
+            None => libpostal_setup(),
         }
+    };
+    #[cfg(feature = "tracing")]
+    tracing::info!(
+        success,
+        duration_ms = started.elapsed().as_millis() as u64,
+        "set up base data"
+    );
+
+    if success {
+        Ok(())
+    } else {
+        Err(call_failed(
+            "failed to set up the base data".to_string(),
+            SetupCall::Base,
+            data_dir,
+            c_data_dir.is_some(),
+        ))
+    }
+}
+
+/// `data_dir` as a C string if it holds the libpostal data files, `None` if it doesn't.
+fn available_data_dir(data_dir: &Path) -> std::result::Result<Option<CString>, SetupFailure> {
+    if !crate::data::DataManager::with_data_dir(data_dir).is_data_available() {
+        return Ok(None);
+    }
+    CString::new(data_dir.to_string_lossy().as_ref())
+        .map(Some)
+        .map_err(|_| SetupFailure {
+            message: "invalid data directory path".to_string(),
+            cause: Arc::new(Error::data_error(format!(
+                "data directory {} contains a NUL byte",
+                data_dir.display()
+            ))),
+        })
+}
+
+/// Failure of a setup call, explaining missing data when libpostal fell back to its defaults.
+fn call_failed(message: String, call: SetupCall, data_dir: &Path, had_data: bool) -> SetupFailure {
+    let cause = if had_data {
+        Error::setup_failed(call, data_dir)
+    } else {
+        Error::data_error(format!(
+            "data files not found in {}. Run data download first.",
+            data_dir.display()
+        ))
+    };
+    SetupFailure {
+        message,
+        cause: Arc::new(cause),
+    }
+}
+
+/// Compare two data directories, resolving symlinks and relative paths when possible.
+fn same_path(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
     }
-    Ok(())
 }
 
 /// Parse an address using libpostal.
@@ -266,12 +742,13 @@ pub(crate) fn teardown() -> Result<()> {
 ///     println!("{}: {}", component.label, component.value);
 /// }
 /// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all, fields(input_len = address.len())))]
 pub(crate) fn parse_address(
     address: &str,
     options: Option<&ParseOptions>,
 ) -> Result<Vec<AddressComponent>> {
-    // Ensure libpostal is initialized
-    initialize()?;
+    // Ensure libpostal and the parser model are initialized
+    let _lifecycle = require(&[Subsystem::Parser])?;
 
     let c_address =
         CString::new(address).map_err(|_| Error::ffi_error("Invalid address string"))?;
@@ -374,19 +851,28 @@ pub(crate) fn parse_address(
 ///     println!("Normalized: {}", variant);
 /// }
 /// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all, fields(input_len = input.len())))]
 pub(crate) fn normalize_string(
     input: &str,
     options: Option<&NormalizeOptions>,
 ) -> Result<Vec<String>> {
-    // Ensure libpostal is initialized
-    initialize()?;
+    // Ensure libpostal is initialized. Without language hints libpostal
+    // classifies the input's language first, which needs the classifier.
+    let _lifecycle = if options.is_none_or(|opts| opts.languages.is_empty()) {
+        require(&[Subsystem::Expand, Subsystem::LanguageClassifier])?
+    } else {
+        require(&[Subsystem::Expand])?
+    };
 
     let c_input = CString::new(input).map_err(|_| Error::ffi_error("Invalid input string"))?;
 
     unsafe {
-        // Get default options or use provided ones
+        // Get default options or use provided ones; the holders keep the
+        // language strings alive until expansion is done
+        let mut language_cstrs = Vec::new();
+        let mut language_ptrs = Vec::new();
         let opts = if let Some(opts) = options {
-            convert_normalize_options(opts)?
+            convert_normalize_options(opts, &mut language_cstrs, &mut language_ptrs)?
         } else {
             libpostal_get_default_options()
         };
@@ -420,6 +906,62 @@ pub(crate) fn normalize_string(
     }
 }
 
+/// Classify the language of an address string using libpostal.
+///
+/// Returns the candidate languages with their probabilities, most likely
+/// first. An empty vector means libpostal could not classify the input.
+///
+/// # Memory Safety
+///
+/// - Input strings are safely converted to null-terminated C strings
+/// - The C-allocated classifier response is freed after conversion
+///
+/// # Thread Safety
+///
+/// This function is thread-safe once libpostal has been initialized.
+///
+/// # Errors
+///
+/// Returns `Error` if:
+/// - libpostal is not initialized
+/// - Input string contains null bytes
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all, fields(input_len = input.len())))]
+pub(crate) fn classify_language(input: &str) -> Result<Vec<LanguageClassification>> {
+    // Ensure libpostal and the language classifier are initialized
+    let _lifecycle = require(&[Subsystem::LanguageClassifier])?;
+
+    let c_input = CString::new(input).map_err(|_| Error::ffi_error("Invalid input string"))?;
+
+    unsafe {
+        let response_ptr = libpostal_classify_language(c_input.as_ptr() as *mut _);
+
+        if response_ptr.is_null() {
+            return Ok(Vec::new()); // No classification is valid
+        }
+
+        let response = &*response_ptr;
+
+        // Convert C results to Rust
+        let mut results = Vec::with_capacity(response.num_languages);
+        for i in 0..response.num_languages {
+            let language_ptr = *response.languages.add(i);
+            if language_ptr.is_null() {
+                continue;
+            }
+
+            results.push(LanguageClassification {
+                language: CStr::from_ptr(language_ptr).to_string_lossy().into_owned(),
+                probability: *response.probs.add(i),
+            });
+        }
+
+        // Cleanup C memory
+        libpostal_language_classifier_response_destroy(response_ptr);
+
+        Ok(results)
+    }
+}
+
 /// Convert Rust ParseOptions to C libpostal_address_parser_options_t.
 ///
 /// This function safely converts Rust parsing options to the C structure
@@ -479,22 +1021,24 @@ fn convert_parse_options(
 /// Convert Rust NormalizeOptions to C libpostal_normalize_options_t.
 ///
 /// This function safely converts Rust normalization options to the C structure
-/// expected by libpostal. Unlike parse options, normalize options only contain
-/// primitive types, so no special string lifetime management is required.
+/// expected by libpostal. The language hints are C strings that the returned
+/// struct points into, so they are stored in the holders passed in.
 ///
 /// # Arguments
 ///
 /// * `options` - The Rust normalization options to convert
+/// * `language_cstrs` - Storage for the language strings
+/// * `language_ptrs` - Storage for the array of pointers to them
 ///
 /// # Memory Safety
 ///
-/// This function is memory-safe as it only copies primitive boolean and integer
-/// values from the Rust struct to the C struct. No pointers or allocations are involved.
+/// The holders must outlive every use of the returned struct, since its
+/// `languages` array points into them.
 ///
 /// # Returns
 ///
 /// Returns a C options struct with the normalized settings, or an error if
-/// the conversion fails (currently this function cannot fail).
+/// a language contains a NUL byte.
 ///
 /// # Safety
 ///
@@ -502,6 +1046,8 @@ fn convert_parse_options(
 /// `libpostal_get_default_options()`, but the conversion itself is safe.
 unsafe fn convert_normalize_options(
     options: &NormalizeOptions,
+    language_cstrs: &mut Vec<CString>,
+    language_ptrs: &mut Vec<*mut i8>,
 ) -> Result<libpostal_normalize_options_t> {
     let mut opts = unsafe { libpostal_get_default_options() };
 
@@ -525,8 +1071,17 @@ unsafe fn convert_normalize_options(
     opts.expand_numex = options.expand_numex;
     opts.roman_numerals = options.roman_numerals;
 
-    // Future enhancement: Handle languages array conversion if needed
-    // For now, using default languages
+    // Pass language hints; without them libpostal classifies the input
+    if !options.languages.is_empty() {
+        for language in &options.languages {
+            let c_language = CString::new(language.as_str())
+                .map_err(|_| Error::ffi_error("Invalid language string"))?;
+            language_cstrs.push(c_language);
+        }
+        language_ptrs.extend(language_cstrs.iter().map(|l| l.as_ptr() as *mut i8));
+        opts.languages = language_ptrs.as_mut_ptr();
+        opts.num_languages = language_ptrs.len();
+    }
 
     Ok(opts)
 }
@@ -553,6 +1108,7 @@ unsafe fn convert_normalize_options(
 /// This struct owns its string data and is safe to pass between threads.
 /// The strings are guaranteed to be valid UTF-8.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AddressComponent {
     /// Component label indicating the semantic meaning of this address part.
     ///
@@ -566,6 +1122,16 @@ pub struct AddressComponent {
     pub value: String,
 }
 
+/// Language candidate from libpostal's language classifier.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LanguageClassification {
+    /// Language code (e.g., "en", "fr")
+    pub language: String,
+    /// Probability assigned by the classifier, between 0 and 1
+    pub probability: f64,
+}
+
 /// Options for address parsing.
 ///
 /// These options provide hints to libpostal about the expected language
@@ -671,6 +1237,161 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_same_path() {
+        let dir = std::env::temp_dir();
+        assert!(same_path(&dir, &dir.join(".")));
+        assert!(!same_path(&dir, &dir.join("libpostal-rs-missing")));
+        assert!(same_path(
+            Path::new("missing/data"),
+            Path::new("missing/data")
+        ));
+    }
+
+    #[test]
+    fn test_setup_failure_chain() {
+        use std::error::Error as _;
+
+        let mut lifecycle = Lifecycle::new();
+        assert!(matches!(lifecycle.state(), InitState::Uninitialized));
+
+        lifecycle.base = Some(Ok(()));
+        lifecycle.parser = Some(Err(call_failed(
+            "failed to load the address parser".to_string(),
+            SetupCall::Parser,
+            Path::new("/data"),
+            true,
+        )));
+
+        let InitState::Failed(error) = lifecycle.state() else {
+            panic!("expected a failed state");
+        };
+        assert_eq!(
+            error.source().map(|e| e.to_string()).as_deref(),
+            Some("libpostal_setup_parser_datadir failed for data directory /data")
+        );
+    }
+
+    #[test]
+    fn test_failed_setup_is_retried() {
+        let mut lifecycle = Lifecycle::new();
+        lifecycle.data_dir = Some(PathBuf::from("/data"));
+        lifecycle.base = Some(Err(call_failed(
+            "failed to set up the base data".to_string(),
+            SetupCall::Base,
+            Path::new("/data"),
+            false,
+        )));
+
+        // Kept while a handle still refers to it
+        lifecycle.handles = 1;
+        lifecycle.clear_failure();
+        assert!(lifecycle.is_set_up());
+
+        lifecycle.handles = 0;
+        lifecycle.clear_failure();
+        assert!(!lifecycle.is_set_up());
+        assert!(matches!(lifecycle.state(), InitState::Uninitialized));
+    }
+
+    #[test]
+    fn test_handles_and_teardown() {
+        let mut lifecycle = Lifecycle::new();
+        assert!(lifecycle.check_implicit_setup().is_ok());
+
+        lifecycle.register(Subsystems::ALL);
+        lifecycle.register(Subsystems::ALL);
+        assert_eq!(lifecycle.handles, 2);
+
+        lifecycle.unregister(Subsystems::ALL);
+        assert_eq!(lifecycle.handles, 1);
+        assert!(lifecycle.check_implicit_setup().is_ok());
+
+        // The last handle tears libpostal down for good
+        lifecycle.base = Some(Err(call_failed(
+            "failed to set up the base data".to_string(),
+            SetupCall::Base,
+            Path::new("/data"),
+            false,
+        )));
+        lifecycle.unregister(Subsystems::ALL);
+        assert_eq!(lifecycle.handles, 0);
+        assert!(matches!(lifecycle.state(), InitState::Uninitialized));
+        assert!(matches!(
+            lifecycle.check_implicit_setup(),
+            Err(Error::InitializationFailed { .. })
+        ));
+
+        // Unbalanced releases don't underflow
+        lifecycle.unregister(Subsystems::ALL);
+        assert_eq!(lifecycle.handles, 0);
+
+        // A new handle makes libpostal usable again
+        lifecycle.register(Subsystems::ALL);
+        assert!(lifecycle.check_implicit_setup().is_ok());
+    }
+
+    #[test]
+    fn test_disabled_subsystem_error() {
+        let mut lifecycle = Lifecycle::new();
+        lifecycle.register(Subsystems {
+            parser: false,
+            ..Subsystems::ALL
+        });
+
+        assert!(lifecycle.check_enabled(Subsystem::Expand).is_ok());
+        assert!(
+            lifecycle
+                .check_enabled(Subsystem::LanguageClassifier)
+                .is_ok()
+        );
+        let error = lifecycle.check_enabled(Subsystem::Parser).unwrap_err();
+        assert!(matches!(error, Error::SubsystemDisabled { .. }));
+        assert!(error.to_string().contains("enable_parser"));
+    }
+
+    #[test]
+    fn test_enabled_subsystems_follow_live_handles() {
+        let without_parser = Subsystems {
+            parser: false,
+            ..Subsystems::ALL
+        };
+        let mut lifecycle = Lifecycle::new();
+
+        // Set up implicitly by a standalone parser, before any handle
+        lifecycle.base = Some(Err(call_failed(
+            "failed to set up the base data".to_string(),
+            SetupCall::Base,
+            Path::new("/data"),
+            false,
+        )));
+        assert_eq!(lifecycle.enabled(), Subsystems::ALL);
+
+        // The first handle narrows what may be used
+        lifecycle.register(without_parser);
+        assert!(lifecycle.check_enabled(Subsystem::Parser).is_err());
+
+        // Another handle widens it only while it is alive
+        lifecycle.register(Subsystems::ALL);
+        assert!(lifecycle.check_enabled(Subsystem::Parser).is_ok());
+        lifecycle.unregister(Subsystems::ALL);
+        assert!(lifecycle.check_enabled(Subsystem::Parser).is_err());
+
+        // Nothing carries over a teardown
+        lifecycle.unregister(without_parser);
+        assert_eq!(lifecycle.enabled(), Subsystems::NONE);
+        lifecycle.register(Subsystems {
+            language_classifier: false,
+            ..Subsystems::ALL
+        });
+        assert!(lifecycle.check_enabled(Subsystem::Parser).is_ok());
+        assert!(
+            lifecycle
+                .check_enabled(Subsystem::LanguageClassifier)
+                .is_err()
+        );
+    }
+
     /// Test address parsing with basic functionality
     #[test]
     fn test_basic_address_parsing() {
@@ -769,12 +1490,18 @@ mod tests {
                 roman_numerals: true,
             };
 
-            let result = convert_normalize_options(&normalize_opts);
+            let mut language_cstrs = Vec::new();
+            let mut language_ptrs = Vec::new();
+            let result =
+                convert_normalize_options(&normalize_opts, &mut language_cstrs, &mut language_ptrs);
             assert!(
                 result.is_ok(),
                 "Normalize options conversion failed: {:?}",
                 result
             );
+            let converted = result.unwrap();
+            assert_eq!(converted.num_languages, 1);
+            assert_eq!(CStr::from_ptr(*converted.languages).to_str().unwrap(), "en");
         }
     }
 
@@ -824,4 +1551,34 @@ mod tests {
         // All threads should succeed
         assert_eq!(success_count.load(Ordering::SeqCst), 10);
     }
+
+    /// Test that reloading while other threads parse never breaks a call
+    #[test]
+    fn test_reload_during_parses() {
+        use std::thread;
+
+        initialize().expect("Failed to initialize libpostal");
+        let data_dir = read_lifecycle().data_dir.clone().unwrap();
+
+        let parsers: Vec<_> = (0..4)
+            .map(|_| {
+                thread::spawn(|| {
+                    for _ in 0..20 {
+                        let components =
+                            parse_address("123 Main St, New York, NY", None).expect("parse failed");
+                        assert!(!components.is_empty());
+                    }
+                })
+            })
+            .collect();
+
+        for _ in 0..3 {
+            reload(&data_dir).expect("reload failed");
+        }
+
+        for parser in parsers {
+            parser.join().unwrap();
+        }
+        assert!(parse_address("123 Main St", None).is_ok());
+    }
 }
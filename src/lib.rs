@@ -34,16 +34,31 @@
 #![deny(missing_docs)]
 #![warn(rust_2018_idioms)]
 
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod batch;
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(feature = "csv")]
+pub mod csv;
 pub mod data;
 pub mod error;
 pub mod ffi;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod normalizer;
 pub mod parser;
 pub mod profiling;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "server")]
+pub mod server;
 pub mod types;
 
 // Re-export main API
+pub use batch::{BatchFailure, BatchMode, BatchReport};
 pub use error::{Error, Result};
+pub use ffi::{InitState, LanguageClassification, SetupCall};
 pub use normalizer::{AddressNormalizer, NormalizedAddress};
 pub use parser::{AddressParser, ParsedAddress};
 pub use types::*;
@@ -102,6 +117,12 @@ impl LibPostal {
     ///
     /// * `config` - Configuration for libpostal initialization
     ///
+    /// # Errors
+    ///
+    /// libpostal can only be set up from one data directory per process.
+    /// Returns `Error::InitializationFailed` if another `LibPostal` already
+    /// initialized it from a different `data_dir`.
+    ///
     /// # Examples
     ///
     /// ```rust,no_run
@@ -116,9 +137,10 @@ impl LibPostal {
     /// # Ok::<(), libpostal_rs::Error>(())
     /// ```
     pub async fn with_config(config: LibPostalConfig) -> Result<Self> {
+        let data_manager = data::DataManager::with_config(config.data_config.clone());
+
         // Ensure data is available if auto-download is enabled
         if config.auto_download_data {
-            let data_manager = data::DataManager::with_config(config.data_config.clone());
             #[cfg(feature = "runtime-data")]
             {
                 data_manager.ensure_data().await?;
@@ -133,8 +155,25 @@ impl LibPostal {
             }
         }
 
-        // Initialize the FFI layer
-        ffi::initialize()?;
+        // Check the data files before libpostal loads them
+        if config.verify_data_integrity && data_manager.is_data_available() {
+            if config.verify_data_checksums {
+                data_manager.verify_integrity()?.into_result()?;
+            } else {
+                data_manager.verify_data()?;
+            }
+        }
+
+        // Initialize the FFI layer from the current data generation. A process
+        // keeps the generation it loaded until `reload`, even if an install
+        // switched to a newer one meanwhile.
+        let data_dir = match ffi::status() {
+            InitState::Ready { data_dir, .. } if data_dir.starts_with(data_manager.data_dir()) => {
+                data_dir
+            }
+            _ => data_manager.active_data_dir(),
+        };
+        ffi::initialize_with_data_dir(&data_dir, config.subsystems)?;
 
         Ok(Self { config })
     }
@@ -258,12 +297,116 @@ impl LibPostal {
         self.normalizer().normalize(address)
     }
 
+    /// Classify the language of an address string.
+    ///
+    /// Returns candidate languages with probabilities, most likely first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use libpostal_rs::LibPostal;
+    ///
+    /// # async fn run() -> libpostal_rs::Result<()> {
+    /// let postal = LibPostal::new().await?;
+    /// for candidate in postal.classify_language("Rue de la Paix, Paris")? {
+    ///     println!("{}: {:.2}", candidate.language, candidate.probability);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn classify_language(&self, address: &str) -> Result<Vec<LanguageClassification>> {
+        ffi::classify_language(address)
+    }
+
+    /// Reload libpostal from the configured data directory.
+    ///
+    /// Use this after new data was installed there, to switch to its current
+    /// generation. See [`reload_from`](Self::reload_from).
+    pub fn reload(&mut self) -> Result<()> {
+        let data_dir = self.config.data_config.data_dir.clone();
+        self.reload_from(data_dir)
+    }
+
+    /// Tear libpostal down and set it up again from `data_dir`.
+    ///
+    /// In-flight parses and expansions finish against the old data; calls made
+    /// while the reload runs wait for it. The parser and language classifier
+    /// models are loaded again on first use.
+    ///
+    /// libpostal state is process-wide, so this affects every `LibPostal`
+    /// instance. On success this instance's configuration points at
+    /// `data_dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if libpostal cannot be set up from `data_dir`; calls
+    /// then fail until a later reload succeeds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use libpostal_rs::LibPostal;
+    /// use libpostal_rs::data::DataManager;
+    ///
+    /// # async fn run() -> libpostal_rs::Result<()> {
+    /// let mut postal = LibPostal::new().await?;
+    ///
+    /// let fresh = DataManager::with_data_dir("/var/lib/libpostal/2024-06");
+    /// fresh.download_data().await?;
+    /// postal.reload_from(fresh.data_dir())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn reload_from<P: Into<std::path::PathBuf>>(&mut self, data_dir: P) -> Result<()> {
+        let data_dir = data_dir.into();
+        ffi::reload(&data::DataManager::with_data_dir(&data_dir).active_data_dir())?;
+        self.config.data_config.data_dir = data_dir;
+        Ok(())
+    }
+
+    /// Report the state of the process-wide libpostal setup.
+    ///
+    /// On failure the returned error keeps its full chain; walk
+    /// [`std::error::Error::source`] to find the setup call that failed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use libpostal_rs::{InitState, LibPostal};
+    ///
+    /// # async fn run() -> libpostal_rs::Result<()> {
+    /// let postal = LibPostal::new().await?;
+    /// match postal.status() {
+    ///     InitState::Ready { subsystems, data_dir, parser_model } => {
+    ///         println!(
+    ///             "{parser_model} parser loaded: {} from {}",
+    ///             subsystems.parser,
+    ///             data_dir.display()
+    ///         );
+    ///     }
+    ///     InitState::Failed(error) => eprintln!("libpostal failed: {error}"),
+    ///     InitState::Uninitialized => {}
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn status(&self) -> InitState {
+        ffi::status()
+    }
+
     /// Get the configuration used by this instance.
     pub fn config(&self) -> &LibPostalConfig {
         &self.config
     }
 }
 
+impl Drop for LibPostal {
+    /// Release this handle; dropping the last one tears libpostal down.
+    fn drop(&mut self) {
+        ffi::release(self.config.subsystems);
+    }
+}
+
 /// Configuration for LibPostal initialization and behavior.
 ///
 /// This struct allows customizing various aspects of libpostal's behavior,
@@ -273,11 +416,22 @@ pub struct LibPostalConfig {
     /// Whether to automatically download data files if missing
     pub auto_download_data: bool,
 
-    /// Whether to verify data file integrity on startup
+    /// Whether to verify the data files on startup
+    ///
+    /// Checks that the files are present and non-empty, see
+    /// `DataManager::verify_data`.
     pub verify_data_integrity: bool,
 
+    /// Whether the startup check also hashes every data file
+    ///
+    /// See `DataManager::verify_integrity`. Off by default.
+    pub verify_data_checksums: bool,
+
     /// Data management configuration
     pub data_config: data::DataConfig,
+
+    /// libpostal subsystems this instance may use
+    pub subsystems: Subsystems,
 }
 
 impl Default for LibPostalConfig {
@@ -285,7 +439,9 @@ impl Default for LibPostalConfig {
         Self {
             auto_download_data: true,
             verify_data_integrity: true,
+            verify_data_checksums: false,
             data_config: data::DataConfig::default(),
+            subsystems: Subsystems::ALL,
         }
     }
 }
@@ -313,7 +469,9 @@ impl LibPostalConfig {
 pub struct LibPostalConfigBuilder {
     auto_download_data: bool,
     verify_data_integrity: bool,
+    verify_data_checksums: bool,
     data_config: data::DataConfig,
+    subsystems: Subsystems,
 }
 
 impl LibPostalConfigBuilder {
@@ -322,7 +480,9 @@ impl LibPostalConfigBuilder {
         Self {
             auto_download_data: true,
             verify_data_integrity: true,
+            verify_data_checksums: false,
             data_config: data::DataConfig::default(),
+            subsystems: Subsystems::ALL,
         }
     }
 
@@ -332,12 +492,24 @@ impl LibPostalConfigBuilder {
         self
     }
 
-    /// Set whether to verify data file integrity.
+    /// Set whether to verify the data files on startup.
+    ///
+    /// On by default; the check only makes sure the files are present and
+    /// non-empty.
     pub fn verify_data_integrity(mut self, enabled: bool) -> Self {
         self.verify_data_integrity = enabled;
         self
     }
 
+    /// Set whether the startup check also verifies data file checksums.
+    ///
+    /// This reads and hashes all ~2GB of data before libpostal loads it,
+    /// which adds a noticeable delay to every startup. Off by default.
+    pub fn verify_data_checksums(mut self, enabled: bool) -> Self {
+        self.verify_data_checksums = enabled;
+        self
+    }
+
     /// Set the data configuration.
     pub fn data_config(mut self, config: data::DataConfig) -> Self {
         self.data_config = config;
@@ -350,12 +522,55 @@ impl LibPostalConfigBuilder {
         self
     }
 
+    /// Set which model set to install; see [`data::ParserModel`].
+    pub fn parser_model(mut self, model: data::ParserModel) -> Self {
+        self.data_config.parser_model = model;
+        self
+    }
+
+    /// Report data download progress to `callback`.
+    pub fn download_progress(mut self, callback: data::ProgressCallback) -> Self {
+        self.data_config.progress = Some(callback);
+        self
+    }
+
+    /// Set which libpostal subsystems may be used.
+    pub fn subsystems(mut self, subsystems: Subsystems) -> Self {
+        self.subsystems = subsystems;
+        self
+    }
+
+    /// Set whether address expansion (normalization) may be used.
+    pub fn enable_expand(mut self, enabled: bool) -> Self {
+        self.subsystems.expand = enabled;
+        self
+    }
+
+    /// Set whether the address parser may be used.
+    ///
+    /// The parser model is the largest libpostal model; disable it for
+    /// workers that only normalize.
+    pub fn enable_parser(mut self, enabled: bool) -> Self {
+        self.subsystems.parser = enabled;
+        self
+    }
+
+    /// Set whether the language classifier may be used.
+    ///
+    /// Expansion without explicit languages needs the classifier.
+    pub fn enable_language_classifier(mut self, enabled: bool) -> Self {
+        self.subsystems.language_classifier = enabled;
+        self
+    }
+
     /// Build the configuration.
     pub fn build(self) -> LibPostalConfig {
         LibPostalConfig {
             auto_download_data: self.auto_download_data,
             verify_data_integrity: self.verify_data_integrity,
+            verify_data_checksums: self.verify_data_checksums,
             data_config: self.data_config,
+            subsystems: self.subsystems,
         }
     }
 }
@@ -0,0 +1,276 @@
+//! Parsing metrics in the OpenMetrics text format.
+//!
+//! [`MetricsExporter`] renders what a shared [`PerformanceProfiler`] has
+//! recorded, together with the installed data versions and the process's
+//! resident memory, for a service to serve on its `/metrics` endpoint:
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use libpostal_rs::LibPostal;
+//! use libpostal_rs::metrics::{CONTENT_TYPE, MetricsExporter};
+//! use libpostal_rs::profiling::PerformanceProfiler;
+//!
+//! # async fn run() -> libpostal_rs::Result<()> {
+//! let postal = LibPostal::new().await?;
+//! let profiler = Arc::new(PerformanceProfiler::new());
+//! let parser = postal.parser().with_profiler(profiler.clone());
+//!
+//! let exporter = MetricsExporter::new(profiler).with_data_dir("/var/lib/libpostal");
+//! // In the /metrics handler, respond with CONTENT_TYPE and:
+//! let body = exporter.render();
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::{self, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::data::DataManager;
+use crate::profiling::{self, Operation, OperationSummary, PerformanceProfiler};
+
+/// Content type of the rendered metrics.
+pub const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Renders profiler counters, data versions and memory usage as OpenMetrics text.
+#[derive(Debug, Clone)]
+pub struct MetricsExporter {
+    profiler: Arc<PerformanceProfiler>,
+    data_dir: Option<PathBuf>,
+}
+
+impl MetricsExporter {
+    /// Create an exporter for the operations recorded by `profiler`.
+    pub fn new(profiler: Arc<PerformanceProfiler>) -> Self {
+        Self {
+            profiler,
+            data_dir: None,
+        }
+    }
+
+    /// Report the versions of the data installed in `data_dir`.
+    pub fn with_data_dir(mut self, data_dir: impl Into<PathBuf>) -> Self {
+        self.data_dir = Some(data_dir.into());
+        self
+    }
+
+    /// Render every metric, ending with the `# EOF` marker.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        // Writing to a String cannot fail
+        let _ = self.write(&mut out);
+        out
+    }
+
+    fn write(&self, out: &mut String) -> fmt::Result {
+        let operations = [
+            ("parse", self.profiler.operation_summary(Operation::Parse)),
+            (
+                "expand",
+                self.profiler.operation_summary(Operation::Normalize),
+            ),
+        ];
+
+        writeln!(out, "# TYPE libpostal_operations counter")?;
+        writeln!(out, "# HELP libpostal_operations Parse and expand calls.")?;
+        for (operation, summary) in &operations {
+            writeln!(
+                out,
+                "libpostal_operations_total{{operation=\"{operation}\"}} {}",
+                summary.count
+            )?;
+        }
+
+        writeln!(out, "# TYPE libpostal_errors counter")?;
+        writeln!(out, "# HELP libpostal_errors Failed calls by error kind.")?;
+        for (operation, summary) in &operations {
+            for (kind, count) in &summary.errors_by_kind {
+                writeln!(
+                    out,
+                    "libpostal_errors_total{{operation=\"{operation}\",kind=\"{kind}\"}} {count}"
+                )?;
+            }
+        }
+
+        writeln!(out, "# TYPE libpostal_operation_duration_seconds histogram")?;
+        writeln!(out, "# UNIT libpostal_operation_duration_seconds seconds")?;
+        writeln!(
+            out,
+            "# HELP libpostal_operation_duration_seconds Latency of parse and expand calls."
+        )?;
+        for (operation, summary) in &operations {
+            write_latency_histogram(out, operation, summary)?;
+        }
+
+        writeln!(out, "# TYPE libpostal_batch_size histogram")?;
+        writeln!(out, "# HELP libpostal_batch_size Inputs per batch call.")?;
+        for (operation, summary) in &operations {
+            write_batch_histogram(out, operation, summary)?;
+        }
+
+        if let Some(data_dir) = &self.data_dir {
+            let manager = DataManager::with_data_dir(data_dir);
+            let parser_model = manager.installed_parser_model();
+            let mut versions: Vec<_> = manager
+                .installed_versions()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(component, version)| (component.to_string(), version))
+                .collect();
+            versions.sort();
+
+            writeln!(out, "# TYPE libpostal_data info")?;
+            writeln!(out, "# HELP libpostal_data Installed data versions.")?;
+            for (component, version) in versions {
+                writeln!(
+                    out,
+                    "libpostal_data_info{{component=\"{}\",version=\"{}\",parser_model=\"{parser_model}\"}} 1",
+                    escape(&component),
+                    escape(&version)
+                )?;
+            }
+        }
+
+        if let Some(rss) = profiling::resident_set_size() {
+            writeln!(out, "# TYPE libpostal_resident_memory_bytes gauge")?;
+            writeln!(out, "# UNIT libpostal_resident_memory_bytes bytes")?;
+            writeln!(
+                out,
+                "# HELP libpostal_resident_memory_bytes Resident set size of the process."
+            )?;
+            writeln!(out, "libpostal_resident_memory_bytes {rss}")?;
+        }
+
+        writeln!(out, "# EOF")
+    }
+}
+
+fn write_latency_histogram(
+    out: &mut String,
+    operation: &str,
+    summary: &OperationSummary,
+) -> fmt::Result {
+    let mut count = 0;
+    for bucket in &summary.latency_buckets {
+        count += bucket.count;
+        let le = bucket
+            .le
+            .map_or("+Inf".to_string(), |le| float(le.as_secs_f64()));
+        writeln!(
+            out,
+            "libpostal_operation_duration_seconds_bucket{{operation=\"{operation}\",le=\"{le}\"}} {count}"
+        )?;
+    }
+    writeln!(
+        out,
+        "libpostal_operation_duration_seconds_count{{operation=\"{operation}\"}} {count}"
+    )?;
+    writeln!(
+        out,
+        "libpostal_operation_duration_seconds_sum{{operation=\"{operation}\"}} {}",
+        float(summary.total_time.as_secs_f64())
+    )
+}
+
+fn write_batch_histogram(
+    out: &mut String,
+    operation: &str,
+    summary: &OperationSummary,
+) -> fmt::Result {
+    let mut count = 0;
+    for bucket in &summary.batch_sizes {
+        count += bucket.count;
+        let le = bucket.le.map_or("+Inf".to_string(), |le| float(le as f64));
+        writeln!(
+            out,
+            "libpostal_batch_size_bucket{{operation=\"{operation}\",le=\"{le}\"}} {count}"
+        )?;
+    }
+    writeln!(
+        out,
+        "libpostal_batch_size_count{{operation=\"{operation}\"}} {count}"
+    )?;
+    writeln!(
+        out,
+        "libpostal_batch_size_sum{{operation=\"{operation}\"}} {}",
+        summary.batch_items
+    )
+}
+
+/// Format a float the way OpenMetrics spells them, with `1.0` rather than `1`.
+fn float(value: f64) -> String {
+    let text = value.to_string();
+    if text.contains(['.', 'e', 'i', 'N']) {
+        text
+    } else {
+        text + ".0"
+    }
+}
+
+/// Escape a label value.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use std::time::Duration;
+
+    #[test]
+    fn test_render() {
+        let profiler = Arc::new(PerformanceProfiler::new());
+        profiler.record(Operation::Parse, Duration::from_micros(80), 20, None);
+        profiler.record(
+            Operation::Parse,
+            Duration::from_millis(3),
+            20,
+            Some(&Error::parse_error("bad")),
+        );
+        profiler.record_batch(Operation::Parse, 2);
+
+        let data_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            data_dir.path().join("parser_model_file_version"),
+            "senzing-v1.1.0\n",
+        )
+        .unwrap();
+
+        let text = MetricsExporter::new(profiler)
+            .with_data_dir(data_dir.path())
+            .render();
+
+        assert!(text.contains("libpostal_operations_total{operation=\"parse\"} 2\n"));
+        assert!(text.contains("libpostal_operations_total{operation=\"expand\"} 0\n"));
+        assert!(
+            text.contains("libpostal_errors_total{operation=\"parse\",kind=\"parse_error\"} 1\n")
+        );
+        assert!(text.contains(
+            "libpostal_operation_duration_seconds_bucket{operation=\"parse\",le=\"0.0001\"} 1\n"
+        ));
+        assert!(text.contains(
+            "libpostal_operation_duration_seconds_bucket{operation=\"parse\",le=\"+Inf\"} 2\n"
+        ));
+        assert!(text.contains("libpostal_batch_size_bucket{operation=\"parse\",le=\"10.0\"} 1\n"));
+        assert!(text.contains("libpostal_batch_size_sum{operation=\"parse\"} 2\n"));
+        assert!(text.contains(
+            "libpostal_data_info{component=\"parser\",version=\"senzing-v1.1.0\",parser_model=\"senzing\"} 1\n"
+        ));
+        assert!(text.ends_with("# EOF\n"));
+    }
+
+    #[test]
+    fn test_float() {
+        assert_eq!(float(1.0), "1.0");
+        assert_eq!(float(0.00005), "0.00005");
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+}
@@ -1,13 +1,18 @@
 //! Address normalization functionality.
 
+use std::sync::Arc;
+
+use crate::batch::{BatchMode, BatchReport};
 use crate::error::Result;
 use crate::ffi::{self, NormalizeOptions};
+use crate::profiling::{self, Operation, PerformanceProfiler};
 use crate::types::{Language, NormalizationLevel};
 
 /// High-level address normalizer with builder pattern.
 #[derive(Debug)]
 pub struct AddressNormalizer {
     options: NormalizeOptions,
+    profiler: Option<Arc<PerformanceProfiler>>,
 }
 
 impl AddressNormalizer {
@@ -35,6 +40,7 @@ impl AddressNormalizer {
                 expand_numex: true,
                 roman_numerals: true,
             },
+            profiler: None,
         }
     }
 
@@ -89,6 +95,14 @@ impl AddressNormalizer {
         self
     }
 
+    /// Record the latency of every normalization with `profiler`.
+    ///
+    /// The profiler can be shared with other parsers and normalizers.
+    pub fn with_profiler(mut self, profiler: Arc<PerformanceProfiler>) -> Self {
+        self.profiler = Some(profiler);
+        self
+    }
+
     /// Normalize an address string.
     ///
     /// # Arguments
@@ -114,7 +128,10 @@ impl AddressNormalizer {
     /// # Ok::<(), libpostal_rs::Error>(())
     /// ```
     pub fn normalize(&self, input: &str) -> Result<NormalizedAddress> {
-        let expansions = ffi::normalize_string(input, Some(&self.options))?;
+        let profiler = self.profiler.as_deref();
+        let expansions = profiling::profile(profiler, Operation::Normalize, input, || {
+            ffi::normalize_string(input, Some(&self.options))
+        })?;
         Ok(NormalizedAddress {
             original: input.to_string(),
             expansions,
@@ -122,9 +139,71 @@ impl AddressNormalizer {
     }
 
     /// Normalize multiple address strings in batch.
+    ///
+    /// Fails on the first input that cannot be normalized. Use
+    /// [`normalize_batch_report`](Self::normalize_batch_report) to keep going
+    /// past bad rows.
     pub fn normalize_batch(&self, inputs: &[&str]) -> Result<Vec<NormalizedAddress>> {
+        self.record_batch(inputs.len());
         inputs.iter().map(|input| self.normalize(input)).collect()
     }
+
+    /// Normalize multiple address strings in parallel using multiple threads.
+    ///
+    /// Results are returned in the same order as the input, with failed
+    /// normalizations as errors in the result vector.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use libpostal_rs::AddressNormalizer;
+    ///
+    /// let normalizer = AddressNormalizer::new();
+    /// let results = normalizer.normalize_batch_parallel(&["123 Main St", "456 Oak Ave"])?;
+    /// for result in results {
+    ///     match result {
+    ///         Ok(normalized) => println!("Expansions: {:?}", normalized.expansions),
+    ///         Err(e) => println!("Error: {}", e),
+    ///     }
+    /// }
+    /// # Ok::<(), libpostal_rs::Error>(())
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn normalize_batch_parallel(
+        &self,
+        inputs: &[&str],
+    ) -> Result<Vec<Result<NormalizedAddress>>> {
+        use rayon::prelude::*;
+
+        self.record_batch(inputs.len());
+        Ok(inputs
+            .par_iter()
+            .map(|input| self.normalize(input))
+            .collect())
+    }
+
+    /// Normalize multiple address strings and report per-item successes and failures.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - Slice of address strings to normalize
+    /// * `mode` - Whether to stop at, skip, or collect failing inputs
+    pub fn normalize_batch_report(
+        &self,
+        inputs: &[&str],
+        mode: BatchMode,
+    ) -> BatchReport<NormalizedAddress> {
+        self.record_batch(inputs.len());
+        BatchReport::from_results(
+            inputs,
+            inputs.iter().map(|input| self.normalize(input)),
+            mode,
+        )
+    }
+
+    fn record_batch(&self, size: usize) {
+        profiling::profile_batch(self.profiler.as_deref(), Operation::Normalize, size);
+    }
 }
 
 impl Default for AddressNormalizer {
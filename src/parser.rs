@@ -1,13 +1,18 @@
 //! Address parsing functionality.
 
+use std::sync::Arc;
+
+use crate::batch::{BatchMode, BatchReport};
 use crate::error::Result;
 use crate::ffi::{self, AddressComponent, ParseOptions};
+use crate::profiling::{self, Operation, PerformanceProfiler};
 use crate::types::{AddressHint, Country, Language};
 
 /// High-level address parser with idiomatic Rust API.
 #[derive(Debug)]
 pub struct AddressParser {
     options: ParseOptions,
+    profiler: Option<Arc<PerformanceProfiler>>,
 }
 
 impl AddressParser {
@@ -18,6 +23,7 @@ impl AddressParser {
                 language: None,
                 country: None,
             },
+            profiler: None,
         }
     }
 
@@ -44,6 +50,30 @@ impl AddressParser {
         self
     }
 
+    /// Record the latency of every parse with `profiler`.
+    ///
+    /// The profiler can be shared with other parsers and normalizers.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use libpostal_rs::AddressParser;
+    /// use libpostal_rs::profiling::PerformanceProfiler;
+    /// use std::sync::Arc;
+    ///
+    /// let profiler = Arc::new(PerformanceProfiler::new());
+    /// let parser = AddressParser::new().with_profiler(profiler.clone());
+    /// parser.parse_batch(&["123 Main St", "456 Oak Ave"])?;
+    ///
+    /// let summary = profiler.summary();
+    /// println!("p99: {:?}", summary.parse.p99);
+    /// # Ok::<(), libpostal_rs::Error>(())
+    /// ```
+    pub fn with_profiler(mut self, profiler: Arc<PerformanceProfiler>) -> Self {
+        self.profiler = Some(profiler);
+        self
+    }
+
     /// Parse an address string into structured components.
     ///
     /// # Arguments
@@ -69,15 +99,75 @@ impl AddressParser {
     /// # Ok::<(), libpostal_rs::Error>(())
     /// ```
     pub fn parse(&self, address: &str) -> Result<ParsedAddress> {
-        let components = ffi::parse_address(address, Some(&self.options))?;
-        ParsedAddress::from_components(components)
+        self.profiled(address, || {
+            let components = ffi::parse_address(address, Some(&self.options))?;
+            ParsedAddress::from_components(components)
+        })
+    }
+
+    /// Parse an address into raw labeled components, in input order.
+    ///
+    /// Unlike [`parse`](Self::parse), this keeps repeated and unknown labels
+    /// exactly as libpostal returned them.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use libpostal_rs::AddressParser;
+    ///
+    /// let parser = AddressParser::new();
+    /// for component in parser.parse_components("123 Main St, New York, NY 10001")? {
+    ///     println!("{}: {}", component.label, component.value);
+    /// }
+    /// # Ok::<(), libpostal_rs::Error>(())
+    /// ```
+    pub fn parse_components(&self, address: &str) -> Result<Vec<AddressComponent>> {
+        self.profiled(address, || ffi::parse_address(address, Some(&self.options)))
     }
 
     /// Parse multiple addresses in batch for better performance.
+    ///
+    /// Fails on the first address that cannot be parsed. Use
+    /// [`parse_batch_report`](Self::parse_batch_report) to keep going past
+    /// bad rows.
     pub fn parse_batch(&self, addresses: &[&str]) -> Result<Vec<ParsedAddress>> {
+        self.record_batch(addresses.len());
         addresses.iter().map(|addr| self.parse(addr)).collect()
     }
 
+    /// Parse multiple addresses and report per-item successes and failures.
+    ///
+    /// # Arguments
+    ///
+    /// * `addresses` - Slice of address strings to parse
+    /// * `mode` - Whether to stop at, skip, or collect failing addresses
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use libpostal_rs::{AddressParser, BatchMode};
+    ///
+    /// let parser = AddressParser::new();
+    /// let report = parser.parse_batch_report(&["123 Main St", "bad\0row"], BatchMode::Collect);
+    ///
+    /// println!("{} ok, {} failed", report.succeeded(), report.failed());
+    /// for failure in &report.failures {
+    ///     println!("row {}: {}", failure.index, failure.error);
+    /// }
+    /// ```
+    pub fn parse_batch_report(
+        &self,
+        addresses: &[&str],
+        mode: BatchMode,
+    ) -> BatchReport<ParsedAddress> {
+        self.record_batch(addresses.len());
+        BatchReport::from_results(
+            addresses,
+            addresses.iter().map(|addr| self.parse(addr)),
+            mode,
+        )
+    }
+
     /// Parse multiple addresses in parallel using multiple threads.
     ///
     /// This method is more efficient for large batches of addresses as it
@@ -122,12 +212,14 @@ impl AddressParser {
     pub fn parse_batch_parallel(&self, addresses: &[&str]) -> Result<Vec<Result<ParsedAddress>>> {
         use rayon::prelude::*;
 
+        self.record_batch(addresses.len());
         Ok(addresses
             .par_iter()
             .map(|addr| {
                 // Each thread gets its own parser with the same options
                 let parser = AddressParser {
                     options: self.options.clone(),
+                    profiler: self.profiler.clone(),
                 };
                 parser.parse(addr)
             })
@@ -146,7 +238,9 @@ impl AddressParser {
     /// # Returns
     ///
     /// A vector of successfully parsed `ParsedAddress` instances.
-    /// Failed parses are silently ignored.
+    /// Failed parses are silently ignored; use
+    /// [`parse_batch_parallel_report`](Self::parse_batch_parallel_report)
+    /// to find out which addresses failed.
     ///
     /// # Example
     ///
@@ -173,6 +267,42 @@ impl AddressParser {
             .collect::<Vec<_>>();
         Ok(results)
     }
+
+    /// Parse multiple addresses in parallel and report per-item successes and failures.
+    ///
+    /// All addresses are parsed before the mode is applied, so
+    /// [`BatchMode::FailFast`] truncates the report at the first failing
+    /// index but does not cancel work already done on other threads.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use libpostal_rs::{AddressParser, BatchMode};
+    ///
+    /// let parser = AddressParser::new();
+    /// let addresses = vec!["123 Main St, New York, NY", "456 Oak Ave, Los Angeles, CA"];
+    ///
+    /// let report = parser.parse_batch_parallel_report(&addresses, BatchMode::Skip)?;
+    /// println!("Parsed {} of {} addresses", report.succeeded(), report.total);
+    /// # Ok::<(), libpostal_rs::Error>(())
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn parse_batch_parallel_report(
+        &self,
+        addresses: &[&str],
+        mode: BatchMode,
+    ) -> Result<BatchReport<ParsedAddress>> {
+        let results = self.parse_batch_parallel(addresses)?;
+        Ok(BatchReport::from_results(addresses, results, mode))
+    }
+
+    fn profiled<T>(&self, address: &str, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        profiling::profile(self.profiler.as_deref(), Operation::Parse, address, f)
+    }
+
+    fn record_batch(&self, size: usize) {
+        profiling::profile_batch(self.profiler.as_deref(), Operation::Parse, size);
+    }
 }
 
 impl Default for AddressParser {
@@ -231,6 +361,62 @@ pub struct ParsedAddress {
 }
 
 impl ParsedAddress {
+    /// Labels of all named components, in the order they appear in the struct.
+    ///
+    /// Unclassified components are not included; see [`other`](Self::other).
+    pub const LABELS: &'static [&'static str] = &[
+        "house_number",
+        "road",
+        "unit",
+        "level",
+        "staircase",
+        "entrance",
+        "po_box",
+        "postcode",
+        "suburb",
+        "city",
+        "city_district",
+        "island",
+        "state",
+        "state_district",
+        "country_region",
+        "country",
+        "world_region",
+        "category",
+        "near",
+        "toponym",
+    ];
+
+    /// Get a named component by its libpostal label.
+    ///
+    /// Returns `None` if the component is missing or the label is unknown.
+    pub fn get(&self, label: &str) -> Option<&str> {
+        let value = match label {
+            "house_number" => &self.house_number,
+            "road" => &self.road,
+            "unit" => &self.unit,
+            "level" => &self.level,
+            "staircase" => &self.staircase,
+            "entrance" => &self.entrance,
+            "po_box" => &self.po_box,
+            "postcode" => &self.postcode,
+            "suburb" => &self.suburb,
+            "city" => &self.city,
+            "city_district" => &self.city_district,
+            "island" => &self.island,
+            "state" => &self.state,
+            "state_district" => &self.state_district,
+            "country_region" => &self.country_region,
+            "country" => &self.country,
+            "world_region" => &self.world_region,
+            "category" => &self.category,
+            "near" => &self.near,
+            "toponym" => &self.toponym,
+            _ => return None,
+        };
+        value.as_deref()
+    }
+
     /// Create a ParsedAddress from raw FFI components.
     pub(crate) fn from_components(components: Vec<AddressComponent>) -> Result<Self> {
         let mut parsed = ParsedAddress::default();
@@ -351,4 +537,21 @@ mod tests {
         assert_eq!(components.get("road"), Some(&"Main St".to_string()));
         assert!(!parsed.is_empty());
     }
+
+    #[test]
+    fn test_parsed_address_get() {
+        let parsed = ParsedAddress {
+            city: Some("London".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(parsed.get("city"), Some("London"));
+        assert_eq!(parsed.get("road"), None);
+        assert_eq!(parsed.get("not_a_label"), None);
+        assert!(
+            ParsedAddress::LABELS
+                .iter()
+                .all(|label| parsed.get(label).is_some() == (*label == "city"))
+        );
+    }
 }
@@ -1,9 +1,129 @@
 //! Memory profiling and optimization utilities for libpostal-rs
-
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+//!
+//! Allocation counts come from [`TrackingAllocator`], which a binary installs
+//! as its global allocator to opt in. It only sees allocations made through
+//! Rust; libpostal's own model data is allocated by C code, so the process's
+//! resident set size, sampled over time with [`RssSampler`], is the measure
+//! to use for the memory taken by loading the models.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
 
+use crate::error::{Error, Result};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static DEALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static CURRENT_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Global allocator wrapper that counts allocations for [`MemoryTracker`]
+///
+/// Tracking is opt-in: install it in the binary being profiled.
+///
+/// ```
+/// use libpostal_rs::profiling::TrackingAllocator;
+/// use std::alloc::System;
+///
+/// #[global_allocator]
+/// static ALLOCATOR: TrackingAllocator = TrackingAllocator::new(System);
+/// # fn main() {}
+/// ```
+///
+/// Without it, [`MemoryTracker::stats`] reports zeros.
+#[derive(Debug, Default)]
+pub struct TrackingAllocator<A = System> {
+    inner: A,
+}
+
+impl<A> TrackingAllocator<A> {
+    /// Wrap `inner`, counting every allocation made through it
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+}
+
+impl TrackingAllocator {
+    /// Counters of every tracking allocator in the process
+    pub fn stats() -> MemoryStats {
+        let total_allocations = ALLOCATIONS.load(Ordering::Relaxed);
+        let total_deallocations = DEALLOCATIONS.load(Ordering::Relaxed);
+        // Read the peak last: it only grows past what was current before
+        let current_memory_bytes = CURRENT_BYTES.load(Ordering::Relaxed);
+        MemoryStats {
+            peak_memory_bytes: PEAK_BYTES.load(Ordering::Relaxed),
+            current_memory_bytes,
+            total_allocations,
+            total_deallocations,
+            active_allocations: total_allocations.saturating_sub(total_deallocations),
+        }
+    }
+
+    /// Restart peak tracking from the current usage
+    ///
+    /// Call this before the section to measure, then read
+    /// `peak_memory_bytes` after it.
+    pub fn reset_peak() {
+        PEAK_BYTES.store(CURRENT_BYTES.load(Ordering::Relaxed), Ordering::Relaxed);
+    }
+
+    fn record_alloc(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        Self::grow(size);
+    }
+
+    fn record_dealloc(size: usize) {
+        DEALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        CURRENT_BYTES.fetch_sub(size, Ordering::Relaxed);
+    }
+
+    fn grow(size: usize) {
+        let current = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+    }
+}
+
+// SAFETY: every call is forwarded to `inner` unchanged; the counters are
+// updated with atomics and never allocate.
+unsafe impl<A: GlobalAlloc> GlobalAlloc for TrackingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { self.inner.alloc(layout) };
+        if !ptr.is_null() {
+            TrackingAllocator::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { self.inner.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            TrackingAllocator::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { self.inner.dealloc(ptr, layout) };
+        TrackingAllocator::record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { self.inner.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            if new_size >= layout.size() {
+                TrackingAllocator::grow(new_size - layout.size());
+            } else {
+                CURRENT_BYTES.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+            }
+        }
+        new_ptr
+    }
+}
+
 /// Memory usage statistics for tracking allocations
 #[derive(Debug, Clone, Default)]
 pub struct MemoryStats {
@@ -22,7 +142,7 @@ pub struct MemoryStats {
 /// Simple memory tracker for profiling libpostal-rs usage
 #[derive(Debug)]
 pub struct MemoryTracker {
-    stats: Arc<MemoryStats>,
+    baseline: MemoryStats,
     start_time: Instant,
 }
 
@@ -30,14 +150,23 @@ impl MemoryTracker {
     /// Create a new memory tracker
     pub fn new() -> Self {
         Self {
-            stats: Arc::new(MemoryStats::default()),
+            baseline: TrackingAllocator::stats(),
             start_time: Instant::now(),
         }
     }
 
     /// Get current memory statistics
+    ///
+    /// Allocation and deallocation totals count from the tracker's creation;
+    /// current, active and peak usage are process-wide. All of them stay zero
+    /// unless [`TrackingAllocator`] is the global allocator.
     pub fn stats(&self) -> MemoryStats {
-        self.stats.as_ref().clone()
+        let stats = TrackingAllocator::stats();
+        MemoryStats {
+            total_allocations: stats.total_allocations - self.baseline.total_allocations,
+            total_deallocations: stats.total_deallocations - self.baseline.total_deallocations,
+            ..stats
+        }
     }
 
     /// Get elapsed time since tracking started
@@ -47,67 +176,339 @@ impl MemoryTracker {
 
     /// Sample current memory usage from system
     pub fn sample_system_memory(&self) -> Option<usize> {
-        #[cfg(target_os = "linux")]
-        {
-            self.sample_linux_memory()
-        }
-        #[cfg(target_os = "macos")]
-        {
-            self.sample_macos_memory()
-        }
-        #[cfg(target_os = "windows")]
-        {
-            self.sample_windows_memory()
-        }
-        #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
-        {
-            None
-        }
+        resident_set_size()
     }
+}
+
+impl Default for MemoryTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
+/// Resident set size of the current process in bytes
+///
+/// Sampling is supported on Linux only; elsewhere this returns `None`.
+pub fn resident_set_size() -> Option<usize> {
     #[cfg(target_os = "linux")]
-    fn sample_linux_memory(&self) -> Option<usize> {
-        let status = std::fs::read_to_string("/proc/self/status").ok()?;
-        for line in status.lines() {
-            if line.starts_with("VmRSS:") {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 2 {
-                    let kb = parts[1].parse::<usize>().ok()?;
-                    return Some(kb * 1024); // Convert KB to bytes
+    {
+        sample_linux_memory()
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn sample_linux_memory() -> Option<usize> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    for line in status.lines() {
+        if line.starts_with("VmRSS:") {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() >= 2 {
+                let kb = parts[1].parse::<usize>().ok()?;
+                return Some(kb * 1024); // Convert KB to bytes
+            }
+        }
+    }
+    None
+}
+
+/// Samples the resident set size on a background thread
+///
+/// Start one before the work to measure, such as [`LibPostal`] initialization
+/// or a batch of parses, and stop it afterwards:
+///
+/// ```no_run
+/// use libpostal_rs::profiling::{RssSampler, format_bytes};
+/// use std::time::Duration;
+///
+/// let sampler = RssSampler::start(Duration::from_millis(50));
+/// // ... initialize LibPostal, parse a batch ...
+/// let profile = sampler.stop();
+/// if let Some(peak) = profile.peak_bytes() {
+///     println!("peak RSS: {}", format_bytes(peak));
+/// }
+/// ```
+///
+/// Like [`resident_set_size`], this only samples on Linux; on other
+/// platforms the profile comes back empty.
+///
+/// [`LibPostal`]: crate::LibPostal
+#[derive(Debug)]
+pub struct RssSampler {
+    stop: Sender<()>,
+    handle: JoinHandle<Vec<RssSample>>,
+}
+
+impl RssSampler {
+    /// Start sampling every `interval`
+    pub fn start(interval: Duration) -> Self {
+        let (stop, stopped) = mpsc::channel();
+        let handle = std::thread::spawn(move || {
+            let start = Instant::now();
+            let mut samples = Vec::new();
+            let mut stopping = false;
+            while !stopping {
+                if let Some(rss_bytes) = resident_set_size() {
+                    samples.push(RssSample {
+                        elapsed: start.elapsed(),
+                        rss_bytes,
+                    });
                 }
+                // Stopping still takes the sample above once more
+                stopping = !matches!(
+                    stopped.recv_timeout(interval),
+                    Err(RecvTimeoutError::Timeout)
+                );
             }
+            samples
+        });
+        Self { stop, handle }
+    }
+
+    /// Take a last sample, stop the thread and return every sample taken
+    pub fn stop(self) -> RssProfile {
+        let _ = self.stop.send(());
+        let samples = self.handle.join().unwrap_or_default();
+        RssProfile { samples }
+    }
+}
+
+/// One resident set size sample
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RssSample {
+    /// Time since the sampler started
+    pub elapsed: Duration,
+    /// Resident set size in bytes
+    pub rss_bytes: usize,
+}
+
+/// Samples taken by an [`RssSampler`], oldest first
+#[derive(Debug, Clone, Default)]
+pub struct RssProfile {
+    /// The samples; empty where RSS sampling is not supported
+    pub samples: Vec<RssSample>,
+}
+
+impl RssProfile {
+    /// Highest resident set size sampled
+    pub fn peak_bytes(&self) -> Option<usize> {
+        self.samples.iter().map(|sample| sample.rss_bytes).max()
+    }
+
+    /// Growth from the first sample to the highest one
+    pub fn peak_growth_bytes(&self) -> Option<usize> {
+        let first = self.samples.first()?.rss_bytes;
+        Some(self.peak_bytes()?.saturating_sub(first))
+    }
+}
+
+/// Upper bounds of the latency histogram buckets, in microseconds
+pub const LATENCY_BUCKETS_US: [u64; 16] = [
+    50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 25_000, 50_000, 100_000, 250_000, 500_000,
+    1_000_000, 2_500_000, 5_000_000,
+];
+
+/// Upper bounds of the input length buckets, in bytes
+pub const INPUT_LENGTH_BUCKETS: [usize; 6] = [16, 32, 64, 128, 256, 512];
+
+/// Upper bounds of the batch size buckets, in inputs per batch
+pub const BATCH_SIZE_BUCKETS: [usize; 6] = [1, 10, 100, 1_000, 10_000, 100_000];
+
+/// Kind of operation recorded by a [`PerformanceProfiler`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operation {
+    /// Address parsing
+    Parse,
+    /// Address normalization (expansion)
+    Normalize,
+}
+
+/// Counters for one kind of operation
+#[derive(Debug, Default)]
+struct OperationCounters {
+    count: AtomicUsize,
+    errors: AtomicUsize,
+    errors_by_kind: Mutex<BTreeMap<&'static str, usize>>,
+    total_time_us: AtomicU64,
+    max_time_us: AtomicU64,
+    latency: [AtomicUsize; LATENCY_BUCKETS_US.len() + 1],
+    input_lengths: [AtomicUsize; INPUT_LENGTH_BUCKETS.len() + 1],
+    input_length_time_us: [AtomicU64; INPUT_LENGTH_BUCKETS.len() + 1],
+    batch_sizes: [AtomicUsize; BATCH_SIZE_BUCKETS.len() + 1],
+    batch_items: AtomicUsize,
+}
+
+impl OperationCounters {
+    fn record(&self, duration: Duration, input_len: Option<usize>, error: Option<&Error>) {
+        let micros = u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        if let Some(error) = error {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+            *self
+                .errors_by_kind
+                .lock()
+                .unwrap_or_else(|p| p.into_inner())
+                .entry(error.kind())
+                .or_default() += 1;
+        }
+        self.total_time_us.fetch_add(micros, Ordering::Relaxed);
+        self.max_time_us.fetch_max(micros, Ordering::Relaxed);
+
+        let bucket = LATENCY_BUCKETS_US
+            .iter()
+            .position(|&bound| micros <= bound)
+            .unwrap_or(LATENCY_BUCKETS_US.len());
+        self.latency[bucket].fetch_add(1, Ordering::Relaxed);
+
+        if let Some(len) = input_len {
+            let bucket = INPUT_LENGTH_BUCKETS
+                .iter()
+                .position(|&bound| len <= bound)
+                .unwrap_or(INPUT_LENGTH_BUCKETS.len());
+            self.input_lengths[bucket].fetch_add(1, Ordering::Relaxed);
+            self.input_length_time_us[bucket].fetch_add(micros, Ordering::Relaxed);
         }
-        None
     }
 
-    #[cfg(target_os = "macos")]
-    fn sample_macos_memory(&self) -> Option<usize> {
-        // On macOS, we can use the mach API, but for simplicity we'll use a basic approach
-        // In a real implementation, you'd use mach_task_basic_info
-        None
+    fn record_batch(&self, size: usize) {
+        let bucket = BATCH_SIZE_BUCKETS
+            .iter()
+            .position(|&bound| size <= bound)
+            .unwrap_or(BATCH_SIZE_BUCKETS.len());
+        self.batch_sizes[bucket].fetch_add(1, Ordering::Relaxed);
+        self.batch_items.fetch_add(size, Ordering::Relaxed);
     }
 
-    #[cfg(target_os = "windows")]
-    fn sample_windows_memory(&self) -> Option<usize> {
-        // On Windows, we'd use GetProcessMemoryInfo
-        // For now, return None as this requires additional dependencies
-        None
+    fn summary(&self) -> OperationSummary {
+        let count = self.count.load(Ordering::Relaxed);
+        let total_time = Duration::from_micros(self.total_time_us.load(Ordering::Relaxed));
+        let max_time = Duration::from_micros(self.max_time_us.load(Ordering::Relaxed));
+
+        let latency_buckets: Vec<LatencyBucket> = self
+            .latency
+            .iter()
+            .enumerate()
+            .map(|(i, bucket)| LatencyBucket {
+                le: LATENCY_BUCKETS_US
+                    .get(i)
+                    .copied()
+                    .map(Duration::from_micros),
+                count: bucket.load(Ordering::Relaxed),
+            })
+            .collect();
+
+        let input_lengths = self
+            .input_lengths
+            .iter()
+            .zip(&self.input_length_time_us)
+            .enumerate()
+            .map(|(i, (bucket, time_us))| {
+                let count = bucket.load(Ordering::Relaxed);
+                let time_us = time_us.load(Ordering::Relaxed);
+                InputLengthBucket {
+                    max_len: INPUT_LENGTH_BUCKETS.get(i).copied(),
+                    count,
+                    average_time: average(Duration::from_micros(time_us), count),
+                }
+            })
+            .collect();
+
+        let batch_sizes = self
+            .batch_sizes
+            .iter()
+            .enumerate()
+            .map(|(i, bucket)| BatchSizeBucket {
+                le: BATCH_SIZE_BUCKETS.get(i).copied(),
+                count: bucket.load(Ordering::Relaxed),
+            })
+            .collect();
+
+        OperationSummary {
+            count,
+            errors: self.errors.load(Ordering::Relaxed),
+            errors_by_kind: self
+                .errors_by_kind
+                .lock()
+                .unwrap_or_else(|p| p.into_inner())
+                .clone(),
+            total_time,
+            average_time: average(total_time, count),
+            max_time,
+            p50: percentile(&latency_buckets, max_time, 0.50),
+            p95: percentile(&latency_buckets, max_time, 0.95),
+            p99: percentile(&latency_buckets, max_time, 0.99),
+            latency_buckets,
+            input_lengths,
+            batch_sizes,
+            batch_items: self.batch_items.load(Ordering::Relaxed),
+        }
+    }
+
+    fn reset(&self) {
+        self.count.store(0, Ordering::Relaxed);
+        self.errors.store(0, Ordering::Relaxed);
+        self.errors_by_kind
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .clear();
+        self.total_time_us.store(0, Ordering::Relaxed);
+        self.max_time_us.store(0, Ordering::Relaxed);
+        for bucket in &self.latency {
+            bucket.store(0, Ordering::Relaxed);
+        }
+        for bucket in &self.input_lengths {
+            bucket.store(0, Ordering::Relaxed);
+        }
+        for bucket in &self.input_length_time_us {
+            bucket.store(0, Ordering::Relaxed);
+        }
+        for bucket in &self.batch_sizes {
+            bucket.store(0, Ordering::Relaxed);
+        }
+        self.batch_items.store(0, Ordering::Relaxed);
     }
 }
 
-impl Default for MemoryTracker {
-    fn default() -> Self {
-        Self::new()
+fn average(total: Duration, count: usize) -> Duration {
+    u32::try_from(count)
+        .ok()
+        .and_then(|count| total.checked_div(count))
+        .unwrap_or(Duration::ZERO)
+}
+
+/// Estimate a percentile as the upper bound of the bucket it falls in
+fn percentile(buckets: &[LatencyBucket], max_time: Duration, quantile: f64) -> Duration {
+    let count: usize = buckets.iter().map(|bucket| bucket.count).sum();
+    if count == 0 {
+        return Duration::ZERO;
     }
+
+    let rank = ((count as f64 * quantile).ceil() as usize).max(1);
+    let mut seen = 0;
+    for bucket in buckets {
+        seen += bucket.count;
+        if seen >= rank {
+            return bucket.le.map_or(max_time, |le| le.min(max_time));
+        }
+    }
+    max_time
 }
 
 /// Performance profiler for libpostal operations
+///
+/// Share one with [`AddressParser::with_profiler`] and
+/// [`AddressNormalizer::with_profiler`] to record every call automatically.
+///
+/// [`AddressParser::with_profiler`]: crate::AddressParser::with_profiler
+/// [`AddressNormalizer::with_profiler`]: crate::AddressNormalizer::with_profiler
 #[derive(Debug)]
 pub struct PerformanceProfiler {
     memory_tracker: MemoryTracker,
-    operation_count: AtomicUsize,
-    total_parse_time: AtomicUsize,     // microseconds
-    total_normalize_time: AtomicUsize, // microseconds
+    parse: OperationCounters,
+    normalize: OperationCounters,
 }
 
 impl PerformanceProfiler {
@@ -115,55 +516,89 @@ impl PerformanceProfiler {
     pub fn new() -> Self {
         Self {
             memory_tracker: MemoryTracker::new(),
-            operation_count: AtomicUsize::new(0),
-            total_parse_time: AtomicUsize::new(0),
-            total_normalize_time: AtomicUsize::new(0),
+            parse: OperationCounters::default(),
+            normalize: OperationCounters::default(),
         }
     }
 
     /// Record a parsing operation
     pub fn record_parse_operation(&self, duration: Duration) {
-        self.operation_count.fetch_add(1, Ordering::Relaxed);
-        self.total_parse_time
-            .fetch_add(duration.as_micros() as usize, Ordering::Relaxed);
+        self.parse.record(duration, None, None);
     }
 
     /// Record a normalization operation
     pub fn record_normalize_operation(&self, duration: Duration) {
-        self.operation_count.fetch_add(1, Ordering::Relaxed);
-        self.total_normalize_time
-            .fetch_add(duration.as_micros() as usize, Ordering::Relaxed);
+        self.normalize.record(duration, None, None);
+    }
+
+    /// Record an operation on an input of `input_len` bytes
+    ///
+    /// Pass the error the operation returned, if any, to count it by kind.
+    pub fn record(
+        &self,
+        operation: Operation,
+        duration: Duration,
+        input_len: usize,
+        error: Option<&Error>,
+    ) {
+        self.counters(operation)
+            .record(duration, Some(input_len), error);
+    }
+
+    /// Record a batch of `size` inputs
+    ///
+    /// Each input is still recorded as an operation of its own.
+    pub fn record_batch(&self, operation: Operation, size: usize) {
+        self.counters(operation).record_batch(size);
+    }
+
+    /// Run `f` on `input` and record how long it took and whether it failed
+    pub fn measure<T>(
+        &self,
+        operation: Operation,
+        input: &str,
+        f: impl FnOnce() -> Result<T>,
+    ) -> Result<T> {
+        let start = Instant::now();
+        let result = f();
+        let duration = start.elapsed();
+        self.record(operation, duration, input.len(), result.as_ref().err());
+        result
+    }
+
+    /// Summary of one kind of operation
+    pub fn operation_summary(&self, operation: Operation) -> OperationSummary {
+        self.counters(operation).summary()
     }
 
     /// Get performance summary
     pub fn summary(&self) -> PerformanceSummary {
-        let operation_count = self.operation_count.load(Ordering::Relaxed);
-        let total_parse_time_us = self.total_parse_time.load(Ordering::Relaxed);
-        let total_normalize_time_us = self.total_normalize_time.load(Ordering::Relaxed);
+        let parse = self.parse.summary();
+        let normalize = self.normalize.summary();
 
         PerformanceSummary {
-            total_operations: operation_count,
+            total_operations: parse.count + normalize.count,
             total_runtime: self.memory_tracker.elapsed(),
-            average_parse_time: if operation_count > 0 {
-                Duration::from_micros((total_parse_time_us / operation_count) as u64)
-            } else {
-                Duration::ZERO
-            },
-            average_normalize_time: if operation_count > 0 {
-                Duration::from_micros((total_normalize_time_us / operation_count) as u64)
-            } else {
-                Duration::ZERO
-            },
+            average_parse_time: parse.average_time,
+            average_normalize_time: normalize.average_time,
             memory_stats: self.memory_tracker.stats(),
             current_memory_usage: self.memory_tracker.sample_system_memory(),
+            parse,
+            normalize,
         }
     }
 
     /// Reset all counters
     pub fn reset(&self) {
-        self.operation_count.store(0, Ordering::Relaxed);
-        self.total_parse_time.store(0, Ordering::Relaxed);
-        self.total_normalize_time.store(0, Ordering::Relaxed);
+        self.parse.reset();
+        self.normalize.reset();
+    }
+
+    fn counters(&self, operation: Operation) -> &OperationCounters {
+        match operation {
+            Operation::Parse => &self.parse,
+            Operation::Normalize => &self.normalize,
+        }
     }
 }
 
@@ -173,6 +608,30 @@ impl Default for PerformanceProfiler {
     }
 }
 
+/// Run `f`, recording it with `profiler` if there is one
+pub(crate) fn profile<T>(
+    profiler: Option<&PerformanceProfiler>,
+    operation: Operation,
+    input: &str,
+    f: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    match profiler {
+        Some(profiler) => profiler.measure(operation, input, f),
+        None => f(),
+    }
+}
+
+/// Record a batch with `profiler` if there is one
+pub(crate) fn profile_batch(
+    profiler: Option<&PerformanceProfiler>,
+    operation: Operation,
+    size: usize,
+) {
+    if let Some(profiler) = profiler {
+        profiler.record_batch(operation, size);
+    }
+}
+
 /// Summary of performance metrics
 #[derive(Debug, Clone)]
 pub struct PerformanceSummary {
@@ -188,6 +647,73 @@ pub struct PerformanceSummary {
     pub memory_stats: MemoryStats,
     /// Current system memory usage in bytes
     pub current_memory_usage: Option<usize>,
+    /// Parse operations
+    pub parse: OperationSummary,
+    /// Normalize operations
+    pub normalize: OperationSummary,
+}
+
+/// Counters and latencies of one kind of operation
+#[derive(Debug, Clone, Default)]
+pub struct OperationSummary {
+    /// Number of operations recorded
+    pub count: usize,
+    /// Number of those that returned an error
+    pub errors: usize,
+    /// Errors by [`Error::kind`]
+    pub errors_by_kind: BTreeMap<&'static str, usize>,
+    /// Time spent in all of them
+    pub total_time: Duration,
+    /// Average time per operation
+    pub average_time: Duration,
+    /// Slowest operation
+    pub max_time: Duration,
+    /// Median latency, to the resolution of the latency buckets
+    pub p50: Duration,
+    /// 95th percentile latency, to the resolution of the latency buckets
+    pub p95: Duration,
+    /// 99th percentile latency, to the resolution of the latency buckets
+    pub p99: Duration,
+    /// Latency histogram, one entry per bucket of [`LATENCY_BUCKETS_US`]
+    /// plus one for slower operations
+    pub latency_buckets: Vec<LatencyBucket>,
+    /// Operations by input length, one entry per bucket of
+    /// [`INPUT_LENGTH_BUCKETS`] plus one for longer inputs
+    pub input_lengths: Vec<InputLengthBucket>,
+    /// Batches by size, one entry per bucket of [`BATCH_SIZE_BUCKETS`]
+    /// plus one for larger batches
+    pub batch_sizes: Vec<BatchSizeBucket>,
+    /// Number of inputs in all batches
+    pub batch_items: usize,
+}
+
+/// Operations that took longer than the previous bucket's bound and at most `le`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyBucket {
+    /// Upper bound, or `None` for the last, unbounded bucket
+    pub le: Option<Duration>,
+    /// Number of operations in the bucket
+    pub count: usize,
+}
+
+/// Batches larger than the previous bucket's bound and of at most `le` inputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchSizeBucket {
+    /// Upper bound, or `None` for the last, unbounded bucket
+    pub le: Option<usize>,
+    /// Number of batches in the bucket
+    pub count: usize,
+}
+
+/// Operations on inputs longer than the previous bucket's bound and at most `max_len` bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputLengthBucket {
+    /// Upper bound in bytes, or `None` for the last, unbounded bucket
+    pub max_len: Option<usize>,
+    /// Number of operations in the bucket
+    pub count: usize,
+    /// Average time per operation in the bucket
+    pub average_time: Duration,
 }
 
 impl PerformanceSummary {
@@ -237,16 +763,55 @@ mod tests {
         let tracker = MemoryTracker::new();
         let stats = tracker.stats();
 
-        // Should start with zero stats
-        assert_eq!(stats.peak_memory_bytes, 0);
-        assert_eq!(stats.current_memory_bytes, 0);
-        assert_eq!(stats.total_allocations, 0);
+        // Peak usage is never below current usage
+        assert!(stats.peak_memory_bytes >= stats.current_memory_bytes);
 
         // Should have positive elapsed time
         std::thread::sleep(Duration::from_millis(1));
         assert!(tracker.elapsed() > Duration::ZERO);
     }
 
+    #[test]
+    fn test_tracking_allocator() {
+        let allocator = TrackingAllocator::new(System);
+        let tracker = MemoryTracker::new();
+        let layout = Layout::from_size_align(4096, 8).unwrap();
+
+        unsafe {
+            let ptr = allocator.alloc(layout);
+            assert!(!ptr.is_null());
+            assert!(TrackingAllocator::stats().peak_memory_bytes >= 4096);
+            let ptr = allocator.realloc(ptr, layout, 8192);
+            assert!(!ptr.is_null());
+            allocator.dealloc(ptr, Layout::from_size_align(8192, 8).unwrap());
+        }
+
+        let stats = tracker.stats();
+        assert!(stats.total_allocations >= 1);
+        assert!(stats.total_deallocations >= 1);
+        assert!(stats.peak_memory_bytes >= 8192);
+    }
+
+    #[test]
+    fn test_rss_sampler() {
+        let sampler = RssSampler::start(Duration::from_millis(5));
+        let buffer = vec![1u8; 8 * 1024 * 1024];
+        std::thread::sleep(Duration::from_millis(30));
+        let profile = sampler.stop();
+        drop(buffer);
+
+        if cfg!(target_os = "linux") {
+            assert!(profile.samples.len() >= 2);
+            assert!(profile.peak_bytes().unwrap() > 0);
+            assert!(
+                profile
+                    .samples
+                    .windows(2)
+                    .all(|w| w[0].elapsed <= w[1].elapsed)
+            );
+        }
+    }
+
     #[test]
     fn test_performance_profiler() {
         let profiler = PerformanceProfiler::new();
@@ -261,6 +826,60 @@ mod tests {
         assert!(summary.average_normalize_time > Duration::ZERO);
     }
 
+    #[test]
+    fn test_operations_are_counted_separately() {
+        let profiler = PerformanceProfiler::new();
+        for _ in 0..3 {
+            profiler.record_parse_operation(Duration::from_micros(300));
+        }
+        profiler.record_normalize_operation(Duration::from_micros(40));
+
+        let summary = profiler.summary();
+        assert_eq!(summary.total_operations, 4);
+        assert_eq!(summary.parse.count, 3);
+        assert_eq!(summary.normalize.count, 1);
+        assert_eq!(summary.average_parse_time, Duration::from_micros(300));
+        assert_eq!(summary.average_normalize_time, Duration::from_micros(40));
+
+        profiler.reset();
+        assert_eq!(profiler.summary().total_operations, 0);
+    }
+
+    #[test]
+    fn test_percentiles_and_input_lengths() {
+        let profiler = PerformanceProfiler::new();
+        for i in 0..100 {
+            let micros = if i < 90 { 80 } else { 20_000 };
+            profiler.record(Operation::Parse, Duration::from_micros(micros), 20, None);
+        }
+        let result: Result<()> = profiler.measure(Operation::Parse, &"x".repeat(600), || {
+            Err(crate::error::Error::parse_error("too long"))
+        });
+        assert!(result.is_err());
+
+        profiler.record_batch(Operation::Parse, 100);
+
+        let parse = profiler.operation_summary(Operation::Parse);
+        assert_eq!(parse.count, 101);
+        assert_eq!(parse.errors, 1);
+        assert_eq!(parse.errors_by_kind.get("parse_error"), Some(&1));
+        assert_eq!(
+            parse.batch_sizes[2],
+            BatchSizeBucket {
+                le: Some(100),
+                count: 1
+            }
+        );
+        assert_eq!(parse.batch_items, 100);
+        assert_eq!(parse.p50, Duration::from_micros(100));
+        assert_eq!(parse.p95, Duration::from_micros(20_000));
+        assert_eq!(parse.p99, Duration::from_micros(20_000));
+        assert_eq!(parse.latency_buckets.len(), LATENCY_BUCKETS_US.len() + 1);
+        assert_eq!(parse.input_lengths[1].max_len, Some(32));
+        assert_eq!(parse.input_lengths[1].count, 100);
+        assert_eq!(parse.input_lengths.last().unwrap().count, 1);
+    }
+
     #[test]
     fn test_format_bytes() {
         assert_eq!(format_bytes(512), "512 B");
@@ -0,0 +1,328 @@
+//! Python bindings built with PyO3.
+//!
+//! Exposes `LibPostal`, `AddressParser` and `AddressNormalizer` to Python as
+//! the `libpostal_rs` extension module. libpostal is linked statically, so the
+//! wheel built by maturin needs no system libpostal:
+//!
+//! ```text
+//! cd python && maturin build --release
+//! ```
+//!
+//! ```python
+//! from libpostal_rs import LibPostal
+//!
+//! postal = LibPostal()
+//! parsed = postal.parser().parse("123 Main St, New York, NY 10001")
+//! print(parsed["house_number"], parsed["road"], parsed["city"])
+//! print(postal.normalizer().normalize("123 Main St"))
+//! ```
+//!
+//! Parsed addresses are dicts keyed by [`ParsedAddress::LABELS`] plus
+//! `other`; missing fields are `None`. Normalization returns the list of
+//! expansions, like pypostal's `expand_address`. Errors raise
+//! `libpostal_rs.LibPostalError`. The GIL is released while libpostal runs.
+
+use std::path::PathBuf;
+
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::batch::{BatchMode, BatchReport};
+use crate::error::Error;
+use crate::normalizer::{AddressNormalizer, NormalizedAddress};
+use crate::parser::{AddressParser, ParsedAddress};
+use crate::types::{Country, Language, NormalizationLevel};
+use crate::{LibPostal, LibPostalConfig};
+
+create_exception!(
+    libpostal_rs,
+    LibPostalError,
+    PyException,
+    "Raised when libpostal fails to initialize, parse or normalize."
+);
+
+impl From<Error> for PyErr {
+    fn from(error: Error) -> Self {
+        LibPostalError::new_err(error.to_string())
+    }
+}
+
+/// Python handle to an initialized libpostal.
+#[pyclass(name = "LibPostal", module = "libpostal_rs", frozen)]
+pub struct PyLibPostal {
+    inner: LibPostal,
+}
+
+#[pymethods]
+impl PyLibPostal {
+    /// Initialize libpostal, downloading data files into `data_dir` if needed.
+    #[new]
+    #[pyo3(signature = (data_dir = None, auto_download = true))]
+    fn new(py: Python<'_>, data_dir: Option<PathBuf>, auto_download: bool) -> PyResult<Self> {
+        let mut builder = LibPostalConfig::builder().auto_download_data(auto_download);
+        if let Some(dir) = data_dir {
+            builder = builder.data_dir(dir);
+        }
+        let config = builder.build();
+
+        let inner = py.allow_threads(|| -> crate::Result<LibPostal> {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?;
+            runtime.block_on(LibPostal::with_config(config))
+        })?;
+        Ok(Self { inner })
+    }
+
+    /// Create an address parser, optionally hinted with a language and country code.
+    #[pyo3(signature = (language = None, country = None))]
+    fn parser(&self, language: Option<&str>, country: Option<&str>) -> PyAddressParser {
+        PyAddressParser::new(language, country)
+    }
+
+    /// Create an address normalizer.
+    #[pyo3(signature = (languages = None, level = "medium"))]
+    fn normalizer(
+        &self,
+        languages: Option<Vec<String>>,
+        level: &str,
+    ) -> PyResult<PyAddressNormalizer> {
+        PyAddressNormalizer::new(languages, level)
+    }
+
+    /// Parse a single address into a dict of components.
+    fn parse_address<'py>(&self, py: Python<'py>, address: &str) -> PyResult<Bound<'py, PyDict>> {
+        let parsed = py.allow_threads(|| self.inner.parse_address(address))?;
+        parsed_to_dict(py, &parsed)
+    }
+
+    /// Expand a single address into its normalized forms.
+    fn normalize_address(&self, py: Python<'_>, address: &str) -> PyResult<Vec<String>> {
+        let normalized = py.allow_threads(|| self.inner.normalize_address(address))?;
+        Ok(normalized.expansions)
+    }
+
+    /// Classify the language of `text` as `(language, probability)` pairs.
+    fn classify_language(&self, py: Python<'_>, text: &str) -> PyResult<Vec<(String, f64)>> {
+        let classifications = py.allow_threads(|| self.inner.classify_language(text))?;
+        Ok(classifications
+            .into_iter()
+            .map(|c| (c.language, c.probability))
+            .collect())
+    }
+}
+
+/// Python wrapper around [`AddressParser`].
+#[pyclass(name = "AddressParser", module = "libpostal_rs", frozen)]
+pub struct PyAddressParser {
+    inner: AddressParser,
+}
+
+#[pymethods]
+impl PyAddressParser {
+    /// Create a parser, optionally hinted with a language and country code.
+    #[new]
+    #[pyo3(signature = (language = None, country = None))]
+    fn new(language: Option<&str>, country: Option<&str>) -> Self {
+        let mut inner = AddressParser::new();
+        if let Some(language) = language {
+            inner = inner.with_language(Language::from_str(language));
+        }
+        if let Some(country) = country {
+            inner = inner.with_country(Country::from_str(country));
+        }
+        Self { inner }
+    }
+
+    /// Parse a single address into a dict of components.
+    fn parse<'py>(&self, py: Python<'py>, address: &str) -> PyResult<Bound<'py, PyDict>> {
+        let parsed = py.allow_threads(|| self.inner.parse(address))?;
+        parsed_to_dict(py, &parsed)
+    }
+
+    /// Parse many addresses; failed entries are `None` unless `fail_fast` is set.
+    #[pyo3(signature = (addresses, fail_fast = false))]
+    fn parse_batch<'py>(
+        &self,
+        py: Python<'py>,
+        addresses: Vec<String>,
+        fail_fast: bool,
+    ) -> PyResult<Vec<Option<Bound<'py, PyDict>>>> {
+        let inputs: Vec<&str> = addresses.iter().map(String::as_str).collect();
+        let report = py.allow_threads(|| self.inner.parse_batch_report(&inputs, mode(fail_fast)));
+        report_to_list(report, fail_fast, |parsed| parsed_to_dict(py, &parsed))
+    }
+}
+
+/// Python wrapper around [`AddressNormalizer`].
+#[pyclass(name = "AddressNormalizer", module = "libpostal_rs", frozen)]
+pub struct PyAddressNormalizer {
+    inner: AddressNormalizer,
+}
+
+#[pymethods]
+impl PyAddressNormalizer {
+    /// Create a normalizer for `languages` at `level` (`light`, `medium` or `aggressive`).
+    #[new]
+    #[pyo3(signature = (languages = None, level = "medium"))]
+    fn new(languages: Option<Vec<String>>, level: &str) -> PyResult<Self> {
+        let level = match level {
+            "light" => NormalizationLevel::Light,
+            "medium" => NormalizationLevel::Medium,
+            "aggressive" => NormalizationLevel::Aggressive,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown normalization level {other:?}, expected light, medium or aggressive"
+                )));
+            }
+        };
+
+        let mut inner = AddressNormalizer::new().with_level(level);
+        if let Some(languages) = languages {
+            let languages: Vec<Language> = languages
+                .iter()
+                .map(|code| Language::from_str(code))
+                .collect();
+            inner = inner.with_languages(&languages);
+        }
+        Ok(Self { inner })
+    }
+
+    /// Expand a single address into its normalized forms.
+    fn normalize(&self, py: Python<'_>, address: &str) -> PyResult<Vec<String>> {
+        let normalized = py.allow_threads(|| self.inner.normalize(address))?;
+        Ok(normalized.expansions)
+    }
+
+    /// Expand many addresses; failed entries are `None` unless `fail_fast` is set.
+    #[pyo3(signature = (addresses, fail_fast = false))]
+    fn normalize_batch(
+        &self,
+        py: Python<'_>,
+        addresses: Vec<String>,
+        fail_fast: bool,
+    ) -> PyResult<Vec<Option<Vec<String>>>> {
+        let inputs: Vec<&str> = addresses.iter().map(String::as_str).collect();
+        let report =
+            py.allow_threads(|| self.inner.normalize_batch_report(&inputs, mode(fail_fast)));
+        report_to_list(report, fail_fast, |normalized: NormalizedAddress| {
+            Ok(normalized.expansions)
+        })
+    }
+}
+
+fn mode(fail_fast: bool) -> BatchMode {
+    if fail_fast {
+        BatchMode::FailFast
+    } else {
+        BatchMode::Collect
+    }
+}
+
+/// Lay a batch report out as a list in input order, raising on fail-fast failures.
+fn report_to_list<T, U>(
+    report: BatchReport<T>,
+    fail_fast: bool,
+    mut convert: impl FnMut(T) -> PyResult<U>,
+) -> PyResult<Vec<Option<U>>> {
+    if fail_fast && let Some(failure) = report.failures.first() {
+        return Err(LibPostalError::new_err(format!(
+            "item {}: {}",
+            failure.index, failure.error
+        )));
+    }
+
+    let mut values: Vec<Option<U>> = (0..report.total).map(|_| None).collect();
+    for (index, value) in report.successes {
+        values[index] = Some(convert(value)?);
+    }
+    Ok(values)
+}
+
+fn parsed_to_dict<'py>(py: Python<'py>, parsed: &ParsedAddress) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    for label in ParsedAddress::LABELS {
+        dict.set_item(label, parsed.get(label))?;
+    }
+    dict.set_item("other", &parsed.other)?;
+    Ok(dict)
+}
+
+/// The `libpostal_rs` Python module.
+#[pymodule]
+#[pyo3(name = "libpostal_rs")]
+fn python_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyLibPostal>()?;
+    m.add_class::<PyAddressParser>()?;
+    m.add_class::<PyAddressNormalizer>()?;
+    m.add("LibPostalError", m.py().get_type::<LibPostalError>())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> BatchReport<usize> {
+        let inputs = ["a", "", "ccc"];
+        let results = inputs.iter().map(|input| {
+            if input.is_empty() {
+                Err(Error::parse_error("empty"))
+            } else {
+                Ok(input.len())
+            }
+        });
+        BatchReport::from_results(&inputs, results, BatchMode::Collect)
+    }
+
+    #[test]
+    fn test_report_to_list() {
+        pyo3::prepare_freethreaded_python();
+
+        let values = report_to_list(report(), false, Ok).unwrap();
+        assert_eq!(values, vec![Some(1), None, Some(3)]);
+
+        let error = report_to_list(report(), true, Ok).unwrap_err();
+        Python::with_gil(|py| {
+            assert!(error.is_instance_of::<LibPostalError>(py));
+            assert!(error.value(py).to_string().starts_with("item 1: "));
+        });
+    }
+
+    #[test]
+    fn test_parsed_to_dict() {
+        pyo3::prepare_freethreaded_python();
+
+        let parsed = ParsedAddress {
+            road: Some("main st".to_string()),
+            other: vec!["x".to_string()],
+            ..ParsedAddress::default()
+        };
+        Python::with_gil(|py| {
+            let dict = parsed_to_dict(py, &parsed).unwrap();
+            assert_eq!(dict.len(), ParsedAddress::LABELS.len() + 1);
+
+            let road: Option<String> = dict.get_item("road").unwrap().unwrap().extract().unwrap();
+            assert_eq!(road.as_deref(), Some("main st"));
+            assert!(dict.get_item("city").unwrap().unwrap().is_none());
+            let other: Vec<String> = dict.get_item("other").unwrap().unwrap().extract().unwrap();
+            assert_eq!(other, ["x"]);
+        });
+    }
+
+    #[test]
+    fn test_errors_raise_libpostal_error() {
+        pyo3::prepare_freethreaded_python();
+
+        let error = PyErr::from(Error::parse_error("bad"));
+        Python::with_gil(|py| assert!(error.is_instance_of::<LibPostalError>(py)));
+
+        assert!(matches!(mode(true), BatchMode::FailFast));
+        assert!(matches!(mode(false), BatchMode::Collect));
+        assert!(PyAddressNormalizer::new(None, "aggressive").is_ok());
+        let error = PyAddressNormalizer::new(None, "extreme").err().unwrap();
+        Python::with_gil(|py| assert!(error.is_instance_of::<PyValueError>(py)));
+    }
+}
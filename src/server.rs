@@ -0,0 +1,661 @@
+//! HTTP server exposing parsing and expansion over JSON.
+//!
+//! The `/parser` and `/expand` endpoints accept the same request bodies as
+//! [libpostal-rest](https://github.com/johnlonganecker/libpostal-rest), so the
+//! server can stand in for it. On top of those it offers batch endpoints and
+//! separate liveness (`/health`) and readiness (`/ready`) checks. With the
+//! `metrics` feature, `/metrics` serves call counts, latencies and data
+//! versions in the OpenMetrics text format.
+//!
+//! The server starts listening immediately and initializes libpostal in the
+//! background, so `/ready` reports `503` until data is downloaded and loaded,
+//! along with the progress of any download.
+
+use std::future::Future;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::batch::{BatchMode, BatchReport};
+use crate::data::{DataManager, DownloadProgress, ProgressCallback};
+use crate::error::{Error, Result};
+use crate::ffi::AddressComponent;
+use crate::profiling::{Operation, PerformanceProfiler};
+use crate::types::{Country, Language};
+use crate::{InitState, LibPostal, LibPostalConfig};
+
+/// Largest number of queries accepted by a single batch request.
+const MAX_BATCH_SIZE: usize = 10_000;
+
+/// Shared state behind the HTTP handlers.
+#[derive(Debug)]
+pub struct ServerState {
+    config: LibPostalConfig,
+    postal: OnceLock<LibPostal>,
+    init_error: Mutex<Option<String>>,
+    download: Arc<Mutex<Option<DownloadProgress>>>,
+    profiler: Arc<PerformanceProfiler>,
+}
+
+impl ServerState {
+    /// Create server state that has not initialized libpostal yet.
+    pub fn new(config: LibPostalConfig) -> Self {
+        Self {
+            config,
+            postal: OnceLock::new(),
+            init_error: Mutex::new(None),
+            download: Arc::new(Mutex::new(None)),
+            profiler: Arc::new(PerformanceProfiler::new()),
+        }
+    }
+
+    /// Profiler recording the parse and expand calls of every request.
+    pub fn profiler(&self) -> &Arc<PerformanceProfiler> {
+        &self.profiler
+    }
+
+    /// Initialize libpostal, downloading data if the configuration allows it.
+    ///
+    /// Failures and download progress are also recorded so `/ready` can
+    /// report them. A progress callback already in the configuration is
+    /// still called.
+    pub async fn initialize(&self) -> Result<()> {
+        let mut config = self.config.clone();
+        let download = Arc::clone(&self.download);
+        let inner = config.data_config.progress.take();
+        config.data_config.progress = Some(ProgressCallback::new(move |progress| {
+            *download.lock().unwrap_or_else(|p| p.into_inner()) = Some(progress.clone());
+            if let Some(inner) = &inner {
+                inner.emit(progress);
+            }
+        }));
+
+        match LibPostal::with_config(config).await {
+            Ok(postal) => {
+                let _ = self.postal.set(postal);
+                Ok(())
+            }
+            Err(e) => {
+                *self.init_error.lock().unwrap_or_else(|p| p.into_inner()) = Some(e.to_string());
+                Err(e)
+            }
+        }
+    }
+
+    /// Check if libpostal is initialized and its data files are present.
+    pub fn is_ready(&self) -> bool {
+        self.postal.get().is_some()
+            && DataManager::with_config(self.config.data_config.clone()).is_data_available()
+    }
+
+    fn postal(&self) -> std::result::Result<&LibPostal, ApiError> {
+        self.postal.get().ok_or_else(|| {
+            ApiError(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "libpostal is not ready".to_string(),
+            )
+        })
+    }
+}
+
+/// Build the router with all endpoints.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use libpostal_rs::LibPostalConfig;
+/// use libpostal_rs::server::{router, ServerState};
+///
+/// # async fn run() -> libpostal_rs::Result<()> {
+/// let state = Arc::new(ServerState::new(LibPostalConfig::default()));
+/// state.initialize().await?;
+///
+/// let listener = tokio::net::TcpListener::bind("127.0.0.1:8080").await?;
+/// axum::serve(listener, router(state)).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn router(state: Arc<ServerState>) -> Router {
+    let router = Router::new()
+        .route("/parser", post(parse))
+        .route("/parser/batch", post(parse_batch))
+        .route("/expand", post(expand))
+        .route("/expand/batch", post(expand_batch))
+        .route("/health", get(health))
+        .route("/ready", get(ready));
+    #[cfg(feature = "metrics")]
+    let router = router.route("/metrics", get(metrics));
+    router.with_state(state)
+}
+
+/// Serve on `listener` until `shutdown` resolves, then drain in-flight requests.
+///
+/// libpostal is initialized in the background while the server is already
+/// accepting connections.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use libpostal_rs::LibPostalConfig;
+/// use libpostal_rs::server::{serve, shutdown_signal};
+///
+/// # async fn run() -> libpostal_rs::Result<()> {
+/// let listener = tokio::net::TcpListener::bind("127.0.0.1:8080").await?;
+/// serve(listener, LibPostalConfig::default(), shutdown_signal()).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn serve<F>(
+    listener: tokio::net::TcpListener,
+    config: LibPostalConfig,
+    shutdown: F,
+) -> Result<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let state = Arc::new(ServerState::new(config));
+
+    let init_state = Arc::clone(&state);
+    tokio::spawn(async move {
+        // The error is also reported by /ready
+        if let Err(e) = init_state.initialize().await {
+            #[cfg(feature = "tracing")]
+            tracing::error!(error = %e, "libpostal initialization failed");
+            #[cfg(not(feature = "tracing"))]
+            eprintln!("libpostal initialization failed: {e}");
+        }
+    });
+
+    axum::serve(listener, router(state))
+        .with_graceful_shutdown(shutdown)
+        .await?;
+    Ok(())
+}
+
+/// Resolve when the process receives Ctrl-C or, on Unix, SIGTERM.
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+/// Body of `POST /parser`.
+#[derive(Debug, Deserialize)]
+pub struct ParseRequest {
+    /// Address to parse
+    pub query: String,
+    /// Optional language hint
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Optional country hint
+    #[serde(default)]
+    pub country: Option<String>,
+}
+
+/// Body of `POST /parser/batch`.
+#[derive(Debug, Deserialize)]
+pub struct ParseBatchRequest {
+    /// Addresses to parse
+    pub queries: Vec<String>,
+    /// Optional language hint applied to every query
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Optional country hint applied to every query
+    #[serde(default)]
+    pub country: Option<String>,
+}
+
+/// Body of `POST /expand`.
+#[derive(Debug, Deserialize)]
+pub struct ExpandRequest {
+    /// Address to expand
+    pub query: String,
+    /// Optional languages to expand for
+    #[serde(default)]
+    pub languages: Vec<String>,
+}
+
+/// Body of `POST /expand/batch`.
+#[derive(Debug, Deserialize)]
+pub struct ExpandBatchRequest {
+    /// Addresses to expand
+    pub queries: Vec<String>,
+    /// Optional languages applied to every query
+    #[serde(default)]
+    pub languages: Vec<String>,
+}
+
+/// Response of the batch endpoints.
+///
+/// `results` has one entry per query; failed queries are `null` there and
+/// listed in `errors`.
+#[derive(Debug, Serialize)]
+pub struct BatchResponse<T> {
+    /// Per-query results in request order
+    pub results: Vec<Option<T>>,
+    /// Failed queries with their index and message
+    pub errors: Vec<BatchError>,
+    /// Number of queries in the request
+    pub total: usize,
+    /// Number of failed queries
+    pub failed: usize,
+}
+
+/// A failed query in a batch response.
+#[derive(Debug, Serialize)]
+pub struct BatchError {
+    /// Index of the query in the request
+    pub index: usize,
+    /// Error message
+    pub error: String,
+}
+
+impl<T> From<BatchReport<T>> for BatchResponse<T> {
+    fn from(report: BatchReport<T>) -> Self {
+        let failed = report.failed();
+        let mut results: Vec<Option<T>> = (0..report.total).map(|_| None).collect();
+        for (index, value) in report.successes {
+            results[index] = Some(value);
+        }
+
+        BatchResponse {
+            results,
+            errors: report
+                .failures
+                .into_iter()
+                .map(|failure| BatchError {
+                    index: failure.index,
+                    error: failure.error.to_string(),
+                })
+                .collect(),
+            total: report.total,
+            failed,
+        }
+    }
+}
+
+/// Error returned from a handler as `{"error": "..."}`.
+#[derive(Debug)]
+struct ApiError(StatusCode, String);
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        let status = match error {
+            Error::ParseError { .. }
+            | Error::NormalizationError { .. }
+            | Error::FfiError { .. } => StatusCode::BAD_REQUEST,
+            Error::InitializationFailed { .. } | Error::SetupFailed { .. } => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+            Error::SubsystemDisabled { .. } => StatusCode::NOT_IMPLEMENTED,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        ApiError(status, error.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(serde_json::json!({ "error": self.1 }))).into_response()
+    }
+}
+
+type ApiResult<T> = std::result::Result<Json<T>, ApiError>;
+
+/// Run blocking libpostal work off the async executor.
+async fn blocking<T, F>(state: Arc<ServerState>, f: F) -> ApiResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&ServerState, &LibPostal) -> std::result::Result<T, ApiError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || f(&state, state.postal()?))
+        .await
+        .map_err(|e| ApiError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map(Json)
+}
+
+/// Decode a JSON request body.
+///
+/// Like libpostal-rest, the body is read as JSON whatever its content type.
+fn decode<T: DeserializeOwned>(body: &[u8]) -> std::result::Result<T, ApiError> {
+    serde_json::from_slice(body).map_err(|e| {
+        ApiError(
+            StatusCode::BAD_REQUEST,
+            format!("invalid request body: {e}"),
+        )
+    })
+}
+
+fn check_batch_size(len: usize) -> std::result::Result<(), ApiError> {
+    if len > MAX_BATCH_SIZE {
+        return Err(ApiError(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!("batch of {len} queries exceeds the limit of {MAX_BATCH_SIZE}"),
+        ));
+    }
+    Ok(())
+}
+
+fn parser_for(
+    state: &ServerState,
+    postal: &LibPostal,
+    language: Option<&str>,
+    country: Option<&str>,
+) -> crate::AddressParser {
+    let mut parser = postal.parser().with_profiler(Arc::clone(&state.profiler));
+    if let Some(language) = language {
+        parser = parser.with_language(Language::from_str(language));
+    }
+    if let Some(country) = country {
+        parser = parser.with_country(Country::from_str(country));
+    }
+    parser
+}
+
+fn normalizer_for(
+    state: &ServerState,
+    postal: &LibPostal,
+    languages: &[String],
+) -> crate::AddressNormalizer {
+    let normalizer = postal
+        .normalizer()
+        .with_profiler(Arc::clone(&state.profiler));
+    if languages.is_empty() {
+        return normalizer;
+    }
+    let languages: Vec<Language> = languages.iter().map(|l| Language::from_str(l)).collect();
+    normalizer.with_languages(&languages)
+}
+
+async fn parse(
+    State(state): State<Arc<ServerState>>,
+    body: Bytes,
+) -> ApiResult<Vec<AddressComponent>> {
+    let request: ParseRequest = decode(&body)?;
+    blocking(state, move |state, postal| {
+        let parser = parser_for(
+            state,
+            postal,
+            request.language.as_deref(),
+            request.country.as_deref(),
+        );
+        Ok(parser.parse_components(&request.query)?)
+    })
+    .await
+}
+
+async fn parse_batch(
+    State(state): State<Arc<ServerState>>,
+    body: Bytes,
+) -> ApiResult<BatchResponse<Vec<AddressComponent>>> {
+    let request: ParseBatchRequest = decode(&body)?;
+    check_batch_size(request.queries.len())?;
+    blocking(state, move |state, postal| {
+        let parser = parser_for(
+            state,
+            postal,
+            request.language.as_deref(),
+            request.country.as_deref(),
+        );
+        let queries: Vec<&str> = request.queries.iter().map(String::as_str).collect();
+        state.profiler.record_batch(Operation::Parse, queries.len());
+        let report = BatchReport::from_results(
+            &queries,
+            queries.iter().map(|query| parser.parse_components(query)),
+            BatchMode::Collect,
+        );
+        Ok(report.into())
+    })
+    .await
+}
+
+async fn expand(State(state): State<Arc<ServerState>>, body: Bytes) -> ApiResult<Vec<String>> {
+    let request: ExpandRequest = decode(&body)?;
+    blocking(state, move |state, postal| {
+        let normalized =
+            normalizer_for(state, postal, &request.languages).normalize(&request.query)?;
+        Ok(normalized.expansions)
+    })
+    .await
+}
+
+async fn expand_batch(
+    State(state): State<Arc<ServerState>>,
+    body: Bytes,
+) -> ApiResult<BatchResponse<Vec<String>>> {
+    let request: ExpandBatchRequest = decode(&body)?;
+    check_batch_size(request.queries.len())?;
+    blocking(state, move |state, postal| {
+        let normalizer = normalizer_for(state, postal, &request.languages);
+        let queries: Vec<&str> = request.queries.iter().map(String::as_str).collect();
+        state
+            .profiler
+            .record_batch(Operation::Normalize, queries.len());
+        let report = BatchReport::from_results(
+            &queries,
+            queries
+                .iter()
+                .map(|query| normalizer.normalize(query).map(|n| n.expansions)),
+            BatchMode::Collect,
+        );
+        Ok(report.into())
+    })
+    .await
+}
+
+#[cfg(feature = "metrics")]
+async fn metrics(State(state): State<Arc<ServerState>>) -> Response {
+    use crate::metrics::{CONTENT_TYPE, MetricsExporter};
+
+    let mut exporter = MetricsExporter::new(Arc::clone(&state.profiler));
+    if let Some(postal) = state.postal.get()
+        && let InitState::Ready { data_dir, .. } = postal.status()
+    {
+        exporter = exporter.with_data_dir(data_dir);
+    }
+    (
+        [(axum::http::header::CONTENT_TYPE, CONTENT_TYPE)],
+        exporter.render(),
+    )
+        .into_response()
+}
+
+async fn health() -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "status": "ok" }))
+}
+
+async fn ready(State(state): State<Arc<ServerState>>) -> (StatusCode, Json<serde_json::Value>) {
+    if state.is_ready() {
+        let mut body = serde_json::json!({ "status": "ready" });
+        // Which data this instance runs, for tracking rollouts
+        if let Some(postal) = state.postal.get()
+            && let InitState::Ready {
+                data_dir,
+                parser_model,
+                ..
+            } = postal.status()
+        {
+            let versions: serde_json::Map<String, serde_json::Value> =
+                DataManager::with_data_dir(&data_dir)
+                    .installed_versions()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(component, version)| (component.to_string(), version.into()))
+                    .collect();
+            body["data"] = serde_json::json!({
+                "parser_model": parser_model.to_string(),
+                "versions": versions,
+            });
+        }
+        return (StatusCode::OK, Json(body));
+    }
+
+    let init_error = state
+        .init_error
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .clone();
+    let mut body = match init_error {
+        Some(error) => serde_json::json!({ "status": "failed", "error": error }),
+        None => serde_json::json!({ "status": "initializing" }),
+    };
+
+    let download = state
+        .download
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .clone();
+    if let Some(progress) = download {
+        body["download"] = serde_json::json!({
+            "component": progress.component.to_string(),
+            "stage": progress.stage.to_string(),
+            "downloaded_bytes": progress.downloaded_bytes,
+            "total_bytes": progress.total_bytes,
+            "speed_bps": progress.speed_bps,
+        });
+    }
+    (StatusCode::SERVICE_UNAVAILABLE, Json(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serve an uninitialized state on an ephemeral localhost port.
+    async fn spawn_server() -> String {
+        let config = LibPostalConfig::builder()
+            .auto_download_data(false)
+            .data_dir(std::env::temp_dir().join("libpostal-rs-server-test-missing"))
+            .build();
+        let state = Arc::new(ServerState::new(config));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, router(state)).await.unwrap();
+        });
+        format!("http://{addr}")
+    }
+
+    async fn post_json(url: String, body: serde_json::Value) -> reqwest::Response {
+        reqwest::Client::new()
+            .post(url)
+            .header("content-type", "application/json")
+            .body(body.to_string())
+            .send()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_health_and_ready() {
+        let base = spawn_server().await;
+
+        let health = reqwest::get(format!("{base}/health")).await.unwrap();
+        assert_eq!(health.status(), 200);
+
+        let ready = reqwest::get(format!("{base}/ready")).await.unwrap();
+        assert_eq!(ready.status(), 503);
+    }
+
+    #[tokio::test]
+    async fn test_parse_before_ready() {
+        let base = spawn_server().await;
+
+        let response = post_json(
+            format!("{base}/parser"),
+            serde_json::json!({ "query": "123 Main St" }),
+        )
+        .await;
+        assert_eq!(response.status(), 503);
+
+        let body: serde_json::Value =
+            serde_json::from_str(&response.text().await.unwrap()).unwrap();
+        assert!(body["error"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_accepts_body_without_content_type() {
+        let base = spawn_server().await;
+
+        // What `curl -d` sends, as libpostal-rest clients do
+        let response = reqwest::Client::new()
+            .post(format!("{base}/expand"))
+            .body(r#"{"query": "123 Main St"}"#)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 503);
+
+        let response = reqwest::Client::new()
+            .post(format!("{base}/parser"))
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(r#"{"query": "123 Main St"}"#)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 503);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_malformed_requests() {
+        let base = spawn_server().await;
+
+        let response = post_json(
+            format!("{base}/expand"),
+            serde_json::json!({ "address": "missing query field" }),
+        )
+        .await;
+        assert_eq!(response.status(), 400);
+
+        let queries = vec!["x"; MAX_BATCH_SIZE + 1];
+        let response = post_json(
+            format!("{base}/parser/batch"),
+            serde_json::json!({ "queries": queries }),
+        )
+        .await;
+        assert_eq!(response.status(), 413);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[tokio::test]
+    async fn test_metrics() {
+        let base = spawn_server().await;
+
+        let response = reqwest::get(format!("{base}/metrics")).await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.headers()["content-type"],
+            crate::metrics::CONTENT_TYPE
+        );
+        let body = response.text().await.unwrap();
+        assert!(body.contains("libpostal_operations_total{operation=\"parse\"} 0\n"));
+        assert!(body.ends_with("# EOF\n"));
+    }
+}
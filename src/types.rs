@@ -393,6 +393,53 @@ pub enum NormalizationLevel {
     Aggressive,
 }
 
+/// libpostal subsystems that can be enabled independently.
+///
+/// The base data (transliteration, address dictionaries) is always loaded;
+/// these flags control which APIs may be used and which models get loaded.
+/// Enabled models are loaded lazily, the first time an API needs them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Subsystems {
+    /// Address expansion (normalization)
+    pub expand: bool,
+    /// Address parser (loads the parser model, about 2GB in memory)
+    pub parser: bool,
+    /// Language classifier, also used by expansion when no languages are given
+    pub language_classifier: bool,
+}
+
+impl Subsystems {
+    /// Every subsystem enabled.
+    pub const ALL: Self = Self {
+        expand: true,
+        parser: true,
+        language_classifier: true,
+    };
+
+    /// No subsystem enabled.
+    pub const NONE: Self = Self {
+        expand: false,
+        parser: false,
+        language_classifier: false,
+    };
+
+    /// Subsystems enabled in either `self` or `other`.
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            expand: self.expand || other.expand,
+            parser: self.parser || other.parser,
+            language_classifier: self.language_classifier || other.language_classifier,
+        }
+    }
+}
+
+impl Default for Subsystems {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -424,4 +471,20 @@ mod tests {
         assert_eq!(hint.language, Some(Language::English));
         assert_eq!(hint.country, Some(Country::UnitedStates));
     }
+
+    #[test]
+    fn test_subsystems_union() {
+        let parser_only = Subsystems {
+            parser: true,
+            ..Subsystems::NONE
+        };
+        let expand_only = Subsystems {
+            expand: true,
+            ..Subsystems::NONE
+        };
+
+        let both = parser_only.union(expand_only);
+        assert!(both.parser && both.expand && !both.language_classifier);
+        assert_eq!(Subsystems::default(), Subsystems::ALL);
+    }
 }